@@ -0,0 +1,64 @@
+/// Generates the module list for the in-process runner in `src/all_days.rs`, with every module
+/// in `src/bin` that calls `solution!`. Only builds with the `all-days` feature need it.
+use std::{env, fs, path::Path};
+
+#[allow(dead_code)]
+#[path = "src/template/solution_meta.rs"]
+mod solution_meta;
+
+use solution_meta::{BIN_DIR, SolutionMeta};
+
+/// Includes every day as a module and lists the function `solution!` defines to run its parts.
+fn render_all_days(solutions: &[SolutionMeta], bin_dir: &Path) -> String {
+    let mut out = String::new();
+    for solution in solutions {
+        let path = bin_dir.join(format!("{:02}.rs", solution.day));
+        out.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod day{:02};\n",
+            path.display().to_string(),
+            solution.day
+        ));
+    }
+
+    out.push_str("\nstatic SOLUTIONS: &[Solution] = &[\n");
+    for solution in solutions {
+        out.push_str(&format!(
+            "    (advent_of_code::day!({}), day{:02}::run_parts),\n",
+            solution.day, solution.day
+        ));
+    }
+    out.push_str("];\n");
+    out
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/template/solution_meta.rs");
+
+    // watching the days reruns this script, and so rebuilds the library and every day, whenever
+    // one of them is edited. Only pay for that when the days are linked into `all_days`.
+    let solutions = if env::var_os("CARGO_FEATURE_ALL_DAYS").is_some() {
+        println!("cargo:rerun-if-changed={BIN_DIR}");
+        let mut solutions: Vec<SolutionMeta> = fs::read_dir(BIN_DIR)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|file| {
+                let day = solution_meta::day_of_file(file.file_name().to_str()?)?;
+                solution_meta::parse(day, &fs::read_to_string(file.path()).ok()?)
+            })
+            .collect();
+        solutions.sort_by_key(|solution| solution.day);
+        solutions
+    } else {
+        vec![]
+    };
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(BIN_DIR);
+    fs::write(
+        Path::new(&out_dir).join("all_days.rs"),
+        render_all_days(&solutions, &bin_dir),
+    )
+    .unwrap();
+}
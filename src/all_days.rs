@@ -0,0 +1,105 @@
+/// Runs every solution in a single process and reports the end-to-end time for the whole season,
+/// including reading the inputs. Requires the `all-days` feature, e.g. `cargo all --in-process`.
+/// Like the individual days, `--time` benches each part, which is then part of the total.
+/// Days passed as arguments limit the run to those days.
+use std::ffi::OsString;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+use advent_of_code::template::cli::{self, ArgsError};
+use advent_of_code::template::{self, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+#[cfg(feature = "dhat-heap")]
+compile_error!("the in-process runner does not support the `dhat-heap` feature.");
+
+/// A day together with a function that runs its parts on an input.
+type Solution = (Day, fn(&str));
+
+include!(concat!(env!("OUT_DIR"), "/all_days.rs"));
+
+/// Parses `[days...] [--time] [--input <name>] [--example <n>]` and returns the selected days.
+/// The flags are only checked here, each day reads them again when it runs.
+fn parse(args: Vec<OsString>) -> Result<Vec<Day>, ArgsError> {
+    let mut args = pico_args::Arguments::from_vec(args);
+    if args.contains("--submit") {
+        return Err(ArgsError::Conflict("--submit", "the in-process runner"));
+    }
+    args.contains("--time");
+    let _: Option<String> = args.opt_value_from_str("--input")?;
+    let _: Option<usize> = args.opt_value_from_str("--example")?;
+
+    let (flags, free): (Vec<String>, Vec<String>) = args
+        .finish()
+        .into_iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .partition(|arg| arg.starts_with('-'));
+    if !flags.is_empty() {
+        return Err(ArgsError::UnknownFlags(flags));
+    }
+    let mut days = vec![];
+    for value in free {
+        days.extend(cli::parse_days(&value)?.resolve());
+    }
+    Ok(days)
+}
+
+fn main() {
+    let selected = match parse(std::env::args_os().skip(1).collect()) {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    };
+    let timer = Instant::now();
+
+    let solutions = SOLUTIONS
+        .iter()
+        .filter(|(day, _)| selected.is_empty() || selected.contains(day));
+    for (i, (day, run)) in solutions.enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let input = match template::read_input(*day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("No input: {e}");
+                continue;
+            }
+        };
+
+        // keep going with the other days if a solution panics.
+        if panic::catch_unwind(AssertUnwindSafe(|| run(&input))).is_err() {
+            println!("Solution panicked.");
+        }
+    }
+
+    let total_millis = timer.elapsed().as_secs_f64() * 1000.0;
+    println!(
+        "\n{ANSI_BOLD}Total (end-to-end):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    fn days(args: &[&str]) -> Result<Vec<u8>, String> {
+        parse(args.iter().map(Into::into).collect())
+            .map(|days| days.into_iter().map(|day| day.into_inner()).collect())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn parses_days_and_flags() {
+        assert_eq!(days(&[]), Ok(vec![]));
+        assert_eq!(days(&["3", "1-2"]), Ok(vec![3, 1, 2]));
+        assert!(days(&["--example", "2", "--iterations", "5"]).is_err());
+        assert_eq!(days(&["--example", "2", "--time"]), Ok(vec![]));
+        assert_eq!(days(&["--input", "4", "7"]), Ok(vec![7]));
+        assert!(days(&["--submit", "1"]).unwrap_err().contains("--submit"));
+    }
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, title = "Secret Entrance");
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut pos: isize = 50;
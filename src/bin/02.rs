@@ -1,4 +1,6 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, title = "Gift Shop", tags = ["math"]);
+
+use advent_of_code::math::{arithmetic_sum, checked_repeated_pattern, digit_count, pow10};
 
 struct Range {
     start: u64,
@@ -35,18 +37,20 @@ pub fn part_one(input: &str) -> Option<u64> {
         .split(',')
         .map(Range::from_str)
         .for_each(|r| {
-            let upper_power = (r.end as f64).log10().floor() as u32;
+            let upper_power = digit_count(r.end) - 1;
             for power in 1..=upper_power {
-                let base = 10u32.pow(power); // e.g. 10, 100, 1000, ...
-                let pattern = (base + 1) as f64; // e.g. 11, 101, 1001, ...
+                // e.g. 11, 101, 1001, ... - once it no longer fits it's bigger than any ID
+                let Some(pattern) = checked_repeated_pattern(power, 2) else {
+                    break;
+                };
                 let factor_range = Range {
-                    start: (base / 10) as u64,
-                    end: (base - 1) as u64,
+                    start: pow10(power - 1),
+                    end: pow10(power) - 1,
                 }; // e.g. 1-9, 10-99, 100-999, ...
 
                 // Now we calculate the range of valid factors for this pattern
-                let min_factor = (r.start as f64 / pattern).ceil() as u64;
-                let max_factor = (r.end as f64 / pattern).floor() as u64;
+                let min_factor = r.start.div_ceil(pattern);
+                let max_factor = r.end / pattern;
 
                 // Then intersect with the valid factor range
                 let valid_start = min_factor.max(factor_range.start);
@@ -54,10 +58,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
                 // And now we can count the valid factors using the 
                 // arithmetic series formula: n/2 * (first + last)
-                if valid_start <= valid_end {
-                    let count = valid_end - valid_start + 1;
-                    sum += count * (valid_start + valid_end) / 2 * (pattern as u64);
-                }
+                sum += arithmetic_sum(valid_start, valid_end) * pattern;
             }
         });
     Some(sum)
@@ -83,22 +84,22 @@ pub fn part_two(input: &str) -> Option<u64> {
         .split(',')
         .map(Range::from_str)
         .for_each(|r| {
-            let max_digits = (r.end as f64).log10().floor() as u32;
+            let max_digits = digit_count(r.end) - 1;
             for factor_digits in 1..=max_digits {
                 let factor_range = Range {
-                    start: 10u64.pow(factor_digits - 1),
-                    end: 10u64.pow(factor_digits) - 1,
+                    start: pow10(factor_digits - 1),
+                    end: pow10(factor_digits) - 1,
                 }; // e.g. 1-9, 10-99, 100-999, ...
                 for repetitions in 2..=(max_digits/factor_digits + 1) {
                     // Build the pattern
-                    let mut pattern: u64 = 0;
-                    for rep in 0..repetitions {
-                        pattern += 10u64.pow(rep * factor_digits);
-                    } // 10^0 + 10^n + 10^(2n) + ... = 111, 10101010, etc.
+                    // 10^0 + 10^n + 10^(2n) + ... = 111, 10101010, etc.
+                    let Some(pattern) = checked_repeated_pattern(factor_digits, repetitions) else {
+                        break;
+                    };
 
                     // Now we calculate the range of valid factors for this pattern
-                    let min_factor = (r.start as f64 / pattern as f64).ceil() as u64;
-                    let max_factor = (r.end as f64 / pattern as f64).floor() as u64;
+                    let min_factor = r.start.div_ceil(pattern);
+                    let max_factor = r.end / pattern;
 
                     // Then intersect with the valid factor range
                     let valid_start = min_factor.max(factor_range.start);
@@ -1,4 +1,4 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, title = "Lobby", tags = ["greedy"]);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut total = 0;
@@ -1,95 +1,40 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, title = "Printing Department", tags = ["grid", "simulation"]);
 
-use grid::Grid;
+use advent_of_code::bitgrid::{BitGrid, ByteGrid};
+use advent_of_code::cycle::run_until_stable;
+use advent_of_code::viz;
 
-pub fn part_one(input: &str) -> Option<u64> {
-    // Before we construct the grid we need to determine its size
-    let first_line: &str = input.lines().next()?;
-    let line_length = first_line.len();
-    // Now we turn the whole input into a single vector for Grid
-    let numbers: Vec<u32> = input
-        .lines()
-        .flat_map(|line| line.chars().map(|c| if c == '@' { 1 } else { 0 }))
-        .collect();
-    let grid = Grid::from_vec(numbers, line_length);
-
-    let mut count = 0;
-    // For every 1 in the grid, check its neighbors
-    for row in 0..grid.rows() {
-        for col in 0..grid.cols() {
-            if let Some(i) = grid.get(row, col)
-                && *i == 1
-            {
-                let mut neighbor_count = 0;
-                for dx in -1..=1 {
-                    for dy in -1..=1 {
-                        if let Some(value) =
-                            grid.get((row as isize + dx) as usize, (col as isize + dy) as usize)
-                        {
-                            neighbor_count += *value;
-                        }
-                    }
-                }
-                if neighbor_count <= 4 {
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    Some(count)
+fn parse_rolls(input: &str) -> BitGrid {
+    // Pack the rolls into a bitset straight from the input bytes
+    BitGrid::from_bytes(&ByteGrid::new(input), |b| b == b'@')
 }
 
-// Get adjacent coordinates
-// Wraps usize to avoid underflow
-// This is safe because we check bounds when accessing the grid
-fn adj(row: usize, col: usize) -> [(usize, usize); 8] {
-    [
-        (row.wrapping_sub(1), col.wrapping_sub(1)),
-        (row.wrapping_sub(1), col),
-        (row.wrapping_sub(1), col + 1),
-        (row, col.wrapping_sub(1)),
-        (row, col + 1),
-        (row + 1, col.wrapping_sub(1)),
-        (row + 1, col),
-        (row + 1, col + 1),
-    ]
-}
-
-fn count_adj(grid: &Grid<bool>, row: usize, col: usize) -> u32 {
-    let mut count = 0;
-    for (r, c) in adj(row, col) {
-        if let Some(value) = grid.get(r, c) && *value {
-            count += 1;
-        }
-    }
-    count
+pub fn part_one(input: &str) -> Option<u64> {
+    let grid = parse_rolls(input);
+    // A roll can be reached if fewer than 4 of its 8 neighbours are rolls
+    Some(grid.with_fewer_neighbours(4).count_ones())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    // Before we construct the grid we need to determine its size
-    let line_length = input.find('\n').unwrap() + 1;
-    // Now we turn the whole input into a single vector for Grid
-    let numbers: Vec<bool> = input.as_bytes().iter().map(|c| *c == b'@').collect();
-    let mut grid = Grid::from_vec(numbers, line_length);
+    let mut grid = parse_rolls(input);
 
-    let mut changed = true;
+    // Keep removing every reachable roll until a pass removes nothing.
+    // Removing a roll only ever frees up its neighbours, so doing a whole
+    // pass at once ends up removing the same rolls as going one by one
     let mut removed = 0;
-    while changed {
-        changed = false;
-        for row in 0..grid.rows() {
-            for col in 0..grid.cols() {
-                if *grid.get(row, col)? {
-                    let count = count_adj(&grid, row, col);
-                    if count < 4 {
-                        *grid.get_mut(row, col)? = false;
-                        changed = true;
-                        removed += 1;
-                    }
-                }
+    run_until_stable(&mut grid, |grid| {
+        let reachable = grid.with_fewer_neighbours(4);
+        viz::grid(grid.rows(), grid.cols(), |row, col| {
+            match (grid.get(row, col), reachable.get(row, col)) {
+                (_, true) => 'x',
+                (true, false) => '@',
+                (false, false) => '.',
             }
-        }
-    }
+        });
+        removed += reachable.count_ones();
+        grid.remove(&reachable);
+        !reachable.is_empty()
+    });
     Some(removed)
 }
 
@@ -1,4 +1,11 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(
+    5,
+    title = "Cafeteria",
+    tags = ["ranges"],
+    generator = generate
+);
+
+use advent_of_code::template::rng::Rng;
 
 pub fn part_one(input: &str) -> Option<u64> {
     // Split the input into the two sections
@@ -15,9 +22,9 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     // Now we can binary search through the items list to find the right
     // place to check for each range
-    input_parts.get(0)?.lines().for_each(|line| {
+    input_parts.first()?.lines().for_each(|line| {
         // Lines of format XX-YY for arbitrary integers XX and YY
-        let mut parts = line.split('-').into_iter();
+        let mut parts = line.split('-');
         let start: u64 = parts.next().unwrap().parse().unwrap();
         let end: u64 = parts.next().unwrap().parse().unwrap();
         // Find the first item that is >= start
@@ -40,10 +47,10 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     // This time we don't care about the items, just the ranges
     let input_parts: Vec<&str> = input.split("\n\n").collect();
-    let mut ranges: Vec<(u64, u64)> = input_parts.get(0)?
+    let mut ranges: Vec<(u64, u64)> = input_parts.first()?
         .lines()
         .map(|line| {
-            let mut parts = line.split('-').into_iter();
+            let mut parts = line.split('-');
             let start: u64 = parts.next().unwrap().parse().unwrap();
             let end: u64 = parts.next().unwrap().parse().unwrap();
             (start, end)
@@ -79,9 +86,32 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(total)
 }
 
+/// `size` ranges and as many distinct ingredients, shaped like the real input but with smaller
+/// numbers so that the ranges overlap often.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let max = 10 * size as i64 + 10;
+    let mut input = String::new();
+    for _ in 0..size {
+        let start = rng.range(1..=max);
+        let end = start + rng.range(0..=max / 10);
+        input.push_str(&format!("{start}-{end}\n"));
+    }
+    input.push('\n');
+
+    let mut items: Vec<i64> = (0..size).map(|_| rng.range(1..=max)).collect();
+    items.sort_unstable();
+    items.dedup();
+    rng.shuffle(&mut items);
+    for item in items {
+        input.push_str(&format!("{item}\n"));
+    }
+    input
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{DEFAULT_CASES, check_lines, shuffle_lines};
 
     #[test]
     fn test_part_one() {
@@ -94,4 +124,20 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(14));
     }
+
+    #[test]
+    fn part_two_counts_every_fresh_item() {
+        // the ingredients are distinct, so each fresh one is also a fresh ID counted by part two
+        check_lines(DEFAULT_CASES, |rng| generate(20, rng), |input, _| {
+            part_one(input) <= part_two(input)
+        });
+    }
+
+    #[test]
+    fn order_does_not_matter() {
+        check_lines(DEFAULT_CASES, |rng| generate(20, rng), |input, rng| {
+            let shuffled = shuffle_lines(input, rng);
+            part_one(input) == part_one(&shuffled) && part_two(input) == part_two(&shuffled)
+        });
+    }
 }
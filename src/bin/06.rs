@@ -1,4 +1,4 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, title = "Trash Compactor", tags = ["parsing"]);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let lines: Vec<&str> = input.lines().collect();
@@ -1,4 +1,6 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, title = "Laboratories", tags = ["grid", "dp"]);
+
+use advent_of_code::viz;
 
 pub fn part_one(input: &str) -> Option<u64> {
     let first_line = input.lines().next()?;
@@ -10,6 +12,12 @@ pub fn part_one(input: &str) -> Option<u64> {
     rays[start_index] = true;
 
     let mut splits = 0;
+    // Rows drawn so far, only filled in when visualizing
+    let mut drawn: Vec<String> = if viz::enabled() {
+        vec![first_line.to_string()]
+    } else {
+        vec![]
+    };
 
     // Now we parse every OTHER line of the input, starting from the third line
     for line in input.lines().skip(2).step_by(2) {
@@ -29,6 +37,15 @@ pub fn part_one(input: &str) -> Option<u64> {
             });
         // copy split_rays back into rays
         rays.copy_from_slice(&split_rays);
+        viz::frame(|| {
+            let beams: String = line
+                .chars()
+                .zip(&rays)
+                .map(|(c, &ray)| if ray && c == '.' { '|' } else { c })
+                .collect();
+            drawn.push(beams);
+            drawn.join("\n")
+        });
     }
 
     Some(splits)
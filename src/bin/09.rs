@@ -1,30 +1,27 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, title = "Movie Theater", tags = ["geometry"]);
 
-#[derive(Debug, Clone, Copy)]
-struct Point(isize, isize);
+use std::cmp::Reverse;
 
-impl Point {
-    fn transform(&self, basis: (isize, isize)) -> Self {
-        Point(self.0 * basis.0, self.1 * basis.1)
-    }
+use advent_of_code::geometry::{Rect, Vec2, rect_in_rectilinear_polygon};
+
+type Point = Vec2<isize>;
+
+fn transform(p: Point, basis: Point) -> Point {
+    Point::new(p.x * basis.x, p.y * basis.y)
+}
+
+fn parse_tiles(input: &str) -> Vec<Point> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
 }
 
-const NE: (isize, isize) = (-1, 1);
-const NW: (isize, isize) = (1, 1);
-const SE: (isize, isize) = (-1, -1);
-const SW: (isize, isize) = (1, -1);
+const NE: Point = Point::new(-1, 1);
+const NW: Point = Point::new(1, 1);
+const SE: Point = Point::new(-1, -1);
+const SW: Point = Point::new(1, -1);
 
 pub fn part_one(input: &str) -> Option<u64> {
     // Load the tiles from the grid as a sparse matrix
-    let tiles: Vec<Point> = input
-        .lines()
-        .map(|line| {
-            let mut items = line.split(',');
-            Point(
-                items.next().unwrap().parse::<isize>().unwrap(),
-                items.next().unwrap().parse::<isize>().unwrap(),
-            )
-        }).collect();
+    let tiles = parse_tiles(input);
 
     // split the tiles into quadrants based on the origin
     // and remove any that are pareto dominated in their quadrant
@@ -33,21 +30,21 @@ pub fn part_one(input: &str) -> Option<u64> {
         let q_tiles: Vec<Point> = tiles.iter()
             .map(|tile| {
                 // Transform the tile into the ++ quadrant
-                tile.transform(*quadrant)
+                transform(*tile, *quadrant)
             }).collect();
         
-        // println!("Quadrant {}: initial tiles: {:?}", q, q_tiles);
+        advent_of_code::debug!("Quadrant {}: initial tiles: {:?}", q, q_tiles);
         
         // Find the non-dominated pareto frontier in this quadrant
         quadrant_tiles[q] = q_tiles.iter().copied()
             .filter(|&p| {
                 !q_tiles.iter().any(|&q| {
-                    (q.0 >= p.0 && q.1 >= p.1) && (q.0 > p.0 || q.1 > p.1)
+                    (q.x >= p.x && q.y >= p.y) && (q.x > p.x || q.y > p.y)
                 })
-            }).map(|p| p.transform(*quadrant))// transform back to original quadrant
+            }).map(|p| transform(p, *quadrant))// transform back to original quadrant
             .collect();
         
-        // println!("Quadrant {}: {:?}", q, quadrant_tiles[q]);
+        advent_of_code::debug!("Quadrant {}: {:?}", q, quadrant_tiles[q]);
     }
 
     // Now for each pair of quadrants (NE, SW) and (NW, SE)
@@ -58,10 +55,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     // NE and SW
     for ne_tile in quadrant_tiles[0].iter() {
         for sw_tile in quadrant_tiles[3].iter() {
-            let width = ne_tile.0 - sw_tile.0;
-            let height = ne_tile.1 - sw_tile.1;
-            let area = ((width+1).abs() * (height+1).abs()) as u64;
-            // println!("{:?} - {:?} => {} x {} = {}\n", ne_tile, sw_tile, width, height, area);
+            let area = Rect::from_corners(*ne_tile, *sw_tile).area();
             if area > max_area {
                 max_area = area;
             }
@@ -71,10 +65,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     // NW and SE
     for nw_tile in quadrant_tiles[1].iter() {
         for se_tile in quadrant_tiles[2].iter() {
-            let width = nw_tile.0 - se_tile.0;
-            let height = nw_tile.1 - se_tile.1;
-            let area = ((width+1).abs() * (height+1).abs()) as u64;
-            // println!("{:?} - {:?} => {} x {} = {}\n", nw_tile, se_tile, width, height, area);
+            let area = Rect::from_corners(*nw_tile, *se_tile).area();
             if area > max_area {
                 max_area = area;
             }
@@ -85,7 +76,25 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    None
+    // The red tiles are the corners of a rectilinear loop, and every tile on or
+    // inside the loop is red or green. So we want the largest rectangle between
+    // two red tiles that fits entirely inside the polygon.
+    let tiles = parse_tiles(input);
+
+    let mut candidates: Vec<Rect<isize>> = Vec::with_capacity(tiles.len() * tiles.len() / 2);
+    for (i, a) in tiles.iter().enumerate() {
+        for b in &tiles[i + 1..] {
+            candidates.push(Rect::from_corners(*a, *b));
+        }
+    }
+
+    // Checking containment is the expensive bit, so try the biggest ones first
+    // and stop as soon as one fits
+    candidates.sort_unstable_by_key(|rect| Reverse(rect.area()));
+    candidates
+        .into_iter()
+        .find(|rect| rect_in_rectilinear_polygon(rect, &tiles))
+        .map(|rect| rect.area())
 }
 
 #[cfg(test)]
@@ -101,6 +110,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(24));
     }
 }
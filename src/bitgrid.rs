@@ -0,0 +1,313 @@
+//! Compact grid representations for puzzle inputs.
+//!
+//! [`ByteGrid`] indexes straight into the input text without allocating, and [`BitGrid`] packs a
+//! boolean grid into 64-bit words so neighbourhood operations work on 64 cells at a time.
+
+/// A read-only 2D view over the bytes of a puzzle input, skipping line breaks.
+///
+/// Assumes all lines have the same length. Both `\n` and `\r\n` line endings are supported, and
+/// the trailing line break is optional.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteGrid<'a> {
+    data: &'a [u8],
+    rows: usize,
+    cols: usize,
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let data = input.as_bytes();
+        let (cols, stride) = match data.iter().position(|&b| b == b'\n') {
+            Some(i) if i > 0 && data[i - 1] == b'\r' => (i - 1, i + 1),
+            Some(i) => (i, i + 1),
+            None => (data.len(), data.len() + 1),
+        };
+        // Pretend the final line break is present so every row spans exactly `stride` bytes.
+        let rows = if cols == 0 {
+            0
+        } else {
+            (data.len() + stride - cols) / stride
+        };
+        Self {
+            data,
+            rows,
+            cols,
+            stride,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The byte at `(row, col)`, or [`None`] if out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        (row < self.rows && col < self.cols).then(|| self.data[row * self.stride + col])
+    }
+
+    /// The bytes of a single row, without its line break.
+    pub fn row(&self, row: usize) -> &'a [u8] {
+        let start = row * self.stride;
+        &self.data[start..start + self.cols]
+    }
+
+    /// The position of the first occurrence of `byte`, in row-major order.
+    pub fn find(&self, byte: u8) -> Option<(usize, usize)> {
+        let i = self.data.iter().position(|&b| b == byte)?;
+        let (row, col) = (i / self.stride, i % self.stride);
+        (col < self.cols).then_some((row, col))
+    }
+
+    /// Iterates over all cells in row-major order as `((row, col), byte)`.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), u8)> + 'a {
+        let grid = *self;
+        (0..grid.rows).flat_map(move |row| {
+            grid.row(row)
+                .iter()
+                .enumerate()
+                .map(move |(col, &b)| ((row, col), b))
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A boolean grid packed into bits, 64 columns per word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid with every cell unset.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+        Self {
+            rows,
+            cols,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// Creates a grid with the cells set where `predicate` holds for the input byte.
+    pub fn from_bytes(grid: &ByteGrid, predicate: impl Fn(u8) -> bool) -> Self {
+        let mut bits = Self::new(grid.rows(), grid.cols());
+        for row in 0..grid.rows() {
+            for (col, &b) in grid.row(row).iter().enumerate() {
+                if predicate(b) {
+                    bits.set(row, col, true);
+                }
+            }
+        }
+        bits
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Whether `(row, col)` is set. Out of bounds cells are never set.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        row < self.rows
+            && col < self.cols
+            && self.words[row * self.words_per_row + col / WORD_BITS] & (1 << (col % WORD_BITS))
+                != 0
+    }
+
+    /// Sets or clears `(row, col)`. Panics if out of bounds.
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        assert!(row < self.rows && col < self.cols, "cell out of bounds");
+        let word = &mut self.words[row * self.words_per_row + col / WORD_BITS];
+        let mask = 1 << (col % WORD_BITS);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|w| u64::from(w.count_ones())).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Positions of all set cells in row-major order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (row, base) = (i / self.words_per_row, (i % self.words_per_row) * WORD_BITS);
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some((row, base + bit))
+            })
+        })
+    }
+
+    /// Clears every cell that is set in `other`. Both grids must have the same size.
+    pub fn remove(&mut self, other: &BitGrid) {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols));
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= !b;
+        }
+    }
+
+    /// The set cells that have fewer than `k` set cells among their 8 neighbours.
+    ///
+    /// Neighbour counts are kept as four bit planes per word, so each word of 64 cells is
+    /// handled with a fixed number of bitwise operations.
+    pub fn with_fewer_neighbours(&self, k: u8) -> BitGrid {
+        let mut result = Self::new(self.rows, self.cols);
+
+        for row in 0..self.rows {
+            for w in 0..self.words_per_row {
+                let mut planes = [0_u64; 4];
+                for r in [row.wrapping_sub(1), row, row + 1] {
+                    if r >= self.rows {
+                        continue;
+                    }
+                    let (left, centre, right) = self.shifted(r, w);
+                    add_to_planes(&mut planes, left);
+                    add_to_planes(&mut planes, right);
+                    if r != row {
+                        add_to_planes(&mut planes, centre);
+                    }
+                }
+
+                let i = row * self.words_per_row + w;
+                result.words[i] = self.words[i] & less_than(&planes, k);
+            }
+        }
+
+        result
+    }
+
+    /// The word `w` of row `row`, together with copies shifted so that each bit lines up with
+    /// its left and right neighbour respectively.
+    fn shifted(&self, row: usize, w: usize) -> (u64, u64, u64) {
+        let base = row * self.words_per_row;
+        let centre = self.words[base + w];
+        let prev = if w > 0 { self.words[base + w - 1] } else { 0 };
+        let next = if w + 1 < self.words_per_row {
+            self.words[base + w + 1]
+        } else {
+            0
+        };
+        let left = (centre << 1) | (prev >> (WORD_BITS - 1));
+        let right = (centre >> 1) | (next << (WORD_BITS - 1));
+        (left, centre, right)
+    }
+}
+
+/// Adds a 1-bit value to every lane of a bit-sliced 4-bit counter.
+fn add_to_planes(planes: &mut [u64; 4], mut carry: u64) {
+    for plane in planes.iter_mut() {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+}
+
+/// Lanes of a bit-sliced counter whose value is less than `k`.
+fn less_than(planes: &[u64; 4], k: u8) -> u64 {
+    if k > 15 {
+        return u64::MAX;
+    }
+    let mut less = 0;
+    let mut equal = u64::MAX;
+    for bit in (0..4).rev() {
+        if k & (1 << bit) != 0 {
+            less |= equal & !planes[bit];
+            equal &= planes[bit];
+        } else {
+            equal &= !planes[bit];
+        }
+    }
+    less
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_grid_views_input() {
+        let grid = ByteGrid::new("ab\ncd\nef");
+        assert_eq!((grid.rows(), grid.cols()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(b'f'));
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.row(1), b"cd");
+        assert_eq!(grid.find(b'd'), Some((1, 1)));
+        assert_eq!(grid.iter().count(), 6);
+
+        let crlf = ByteGrid::new("ab\r\ncd\r\n");
+        assert_eq!((crlf.rows(), crlf.cols()), (2, 2));
+        assert_eq!(crlf.get(1, 0), Some(b'c'));
+        assert_eq!(crlf.find(b'\r'), None);
+
+        assert_eq!(ByteGrid::new("").rows(), 0);
+    }
+
+    #[test]
+    fn bit_grid_get_set() {
+        let mut grid = BitGrid::new(3, 130);
+        grid.set(0, 0, true);
+        grid.set(1, 64, true);
+        grid.set(2, 129, true);
+        assert!(grid.get(1, 64));
+        assert!(!grid.get(1, 63));
+        assert!(!grid.get(3, 0));
+        assert_eq!(grid.count_ones(), 3);
+        assert_eq!(
+            grid.iter_ones().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 64), (2, 129)]
+        );
+        grid.set(1, 64, false);
+        assert_eq!(grid.count_ones(), 2);
+    }
+
+    #[test]
+    fn counts_neighbours_across_words() {
+        // A solid 3x3 block straddling the word boundary between columns 63 and 64.
+        let mut grid = BitGrid::new(3, 128);
+        for row in 0..3 {
+            for col in 62..65 {
+                grid.set(row, col, true);
+            }
+        }
+
+        // Corners have 3 neighbours, edges 5 and the centre 8.
+        assert_eq!(grid.with_fewer_neighbours(4).count_ones(), 4);
+        assert_eq!(grid.with_fewer_neighbours(6).count_ones(), 8);
+        assert_eq!(grid.with_fewer_neighbours(9).count_ones(), 9);
+        assert!(grid.with_fewer_neighbours(0).is_empty());
+
+        let mut rest = grid.clone();
+        rest.remove(&grid.with_fewer_neighbours(8));
+        assert_eq!(rest.iter_ones().collect::<Vec<_>>(), vec![(1, 63)]);
+    }
+}
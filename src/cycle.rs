@@ -0,0 +1,224 @@
+//! Helpers for simulations: running a state transition to a fixed point, detecting cycles and
+//! extrapolating to far-away steps, plus a memoisation wrapper for recursive counting functions.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Applies `step` until it returns a state equal to its input.
+/// Returns the stable state and the number of steps that changed something.
+pub fn fixed_point<S: PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> (S, usize) {
+    let mut state = initial;
+    let mut steps = 0;
+    loop {
+        let next = step(&state);
+        if next == state {
+            return (state, steps);
+        }
+        state = next;
+        steps += 1;
+    }
+}
+
+/// In-place variant of [`fixed_point`]: calls `step` until it reports that nothing changed.
+/// Returns the number of steps that changed something.
+pub fn run_until_stable<S>(state: &mut S, mut step: impl FnMut(&mut S) -> bool) -> usize {
+    let mut steps = 0;
+    while step(state) {
+        steps += 1;
+    }
+    steps
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The shape of an eventually periodic sequence of states: after `start` steps, the sequence
+/// repeats every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps step `n` onto the earliest step with the same state.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Brent's cycle detection. Only keeps a constant number of states around, at the cost of
+/// computing some of them more than once. The sequence must eventually repeat.
+pub fn brent<S: Clone + PartialEq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Find the cycle length by racing a hare against a tortoise that teleports to it
+    // every power of two steps.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Cycle detection by remembering every state seen. Returns the cycle together with the states
+/// of the first `start + length` steps, which is all that's needed to look up any later step.
+/// The sequence must eventually repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = history.len() - start;
+            return (Cycle { start, length }, history);
+        }
+        seen.insert(state.clone(), history.len());
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` applications of `step`, skipping ahead once the sequence starts repeating.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (cycle, mut history) = find_cycle(initial, step);
+    history.swap_remove(cycle.reduce(n))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Memoises a recursive function. The function receives the memo itself, so recursive calls
+/// go through the cache as well, e.g. fibonacci returns `memo.get(n - 1) + memo.get(n - 2)`.
+pub struct Memo<'f, K, V> {
+    cache: HashMap<K, V>,
+    func: &'f dyn Fn(&mut Memo<'f, K, V>, K) -> V,
+}
+
+impl<'f, K: Clone + Eq + Hash, V: Clone> Memo<'f, K, V> {
+    pub fn new(func: &'f dyn Fn(&mut Memo<'f, K, V>, K) -> V) -> Self {
+        Self {
+            cache: HashMap::new(),
+            func,
+        }
+    }
+
+    /// Returns the cached value for `key`, computing it first if needed.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = (self.func)(self, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, then 3, 4, 5, 6, 7 forever.
+    fn step(&x: &u32) -> u32 {
+        if x < 7 { x + 1 } else { 3 }
+    }
+
+    #[test]
+    fn reaches_fixed_points() {
+        let (state, steps) = fixed_point(100_u32, |&x| x / 2 + x % 2);
+        assert_eq!(state, 1);
+        assert_eq!(steps, 7);
+
+        let mut values = vec![5, 1, 4, 2, 3];
+        let steps = run_until_stable(&mut values, |v| {
+            let swap = v.windows(2).position(|w| w[0] > w[1]);
+            swap.inspect(|&i| v.swap(i, i + 1)).is_some()
+        });
+        assert_eq!(values, vec![1, 2, 3, 4, 5]);
+        assert_eq!(steps, 6);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let expected = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(brent(&0, step), expected);
+        let (cycle, history) = find_cycle(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(history, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(brent(&3, step).start, 0);
+    }
+
+    #[test]
+    fn extrapolates() {
+        assert_eq!(state_at(0, step, 2), 2);
+        assert_eq!(state_at(0, step, 8), 3);
+        assert_eq!(
+            state_at(0, step, 1_000_000_000),
+            3 + (1_000_000_000 - 3) % 5
+        );
+    }
+
+    #[test]
+    fn memoises_recursion() {
+        let paths = |memo: &mut Memo<(u64, u64), u64>, (r, c): (u64, u64)| {
+            if r == 0 || c == 0 {
+                1
+            } else {
+                memo.get((r - 1, c)) + memo.get((r, c - 1))
+            }
+        };
+        let mut memo = Memo::new(&paths);
+        assert_eq!(memo.get((16, 16)), 601_080_390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+
+        let fib = |memo: &mut Memo<u64, u64>, n: u64| {
+            if n < 2 {
+                n
+            } else {
+                memo.get(n - 1) + memo.get(n - 2)
+            }
+        };
+        assert_eq!(Memo::new(&fib).get(90), 2_880_067_194_370_816_120);
+    }
+}
@@ -0,0 +1,521 @@
+//! Integer geometry helpers: 2D/3D vectors, axis-aligned rectangles and polygons.
+//!
+//! All measurements are computed in `i128`, so they never overflow for any of the supported
+//! coordinate types.
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// An integer type usable as a coordinate.
+pub trait Coord: Copy + Ord + Debug + FromStr {
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(impl Coord for $t {
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+        })*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/* -------------------------------------------------------------------------- */
+
+/// A 2D integer vector, used both as a point and as a direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Manhattan (taxicab) distance to `other`.
+    pub fn manhattan(&self, other: &Self) -> u64 {
+        let dx = self.x.to_i128() - other.x.to_i128();
+        let dy = self.y.to_i128() - other.y.to_i128();
+        (dx.unsigned_abs() + dy.unsigned_abs()) as u64
+    }
+
+    /// Squared euclidean distance to `other`.
+    pub fn dist_sq(&self, other: &Self) -> u64 {
+        let dx = self.x.to_i128() - other.x.to_i128();
+        let dy = self.y.to_i128() - other.y.to_i128();
+        (dx * dx + dy * dy) as u64
+    }
+}
+
+impl<T: Coord + Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord + Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord + Mul<Output = T>> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Coord> FromStr for Vec2<T> {
+    type Err = ParseVecError;
+
+    /// Parses a comma separated pair such as `7,3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_components(s)?;
+        Ok(Self::new(x, y))
+    }
+}
+
+/// A 3D integer vector, used both as a point and as a direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Manhattan (taxicab) distance to `other`.
+    pub fn manhattan(&self, other: &Self) -> u64 {
+        let dx = self.x.to_i128() - other.x.to_i128();
+        let dy = self.y.to_i128() - other.y.to_i128();
+        let dz = self.z.to_i128() - other.z.to_i128();
+        (dx.unsigned_abs() + dy.unsigned_abs() + dz.unsigned_abs()) as u64
+    }
+
+    /// Squared euclidean distance to `other`.
+    pub fn dist_sq(&self, other: &Self) -> u64 {
+        let dx = self.x.to_i128() - other.x.to_i128();
+        let dy = self.y.to_i128() - other.y.to_i128();
+        let dz = self.z.to_i128() - other.z.to_i128();
+        (dx * dx + dy * dy + dz * dz) as u64
+    }
+}
+
+impl<T: Coord + Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Coord + Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Coord + Mul<Output = T>> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Coord> FromStr for Vec3<T> {
+    type Err = ParseVecError;
+
+    /// Parses a comma separated triple such as `162,817,812`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_components(s)?;
+        Ok(Self::new(x, y, z))
+    }
+}
+
+fn parse_components<T: Coord, const N: usize>(s: &str) -> Result<[T; N], ParseVecError> {
+    let parts: Vec<T> = s
+        .split(',')
+        .map(|part| part.trim().parse().map_err(|_| ParseVecError))
+        .collect::<Result<_, _>>()?;
+    parts.try_into().map_err(|_| ParseVecError)
+}
+
+/// An error which can be returned when parsing a [`Vec2`] or [`Vec3`].
+#[derive(Debug)]
+pub struct ParseVecError;
+
+impl std::error::Error for ParseVecError {}
+
+impl std::fmt::Display for ParseVecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting comma separated integer coordinates")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An axis-aligned rectangle spanning `min` to `max`, both inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Vec2<T>,
+    pub max: Vec2<T>,
+}
+
+impl<T: Coord> Rect<T> {
+    /// Creates the rectangle spanned by two opposite corners, in any order.
+    pub fn from_corners(a: Vec2<T>, b: Vec2<T>) -> Self {
+        Self {
+            min: Vec2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Vec2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Number of columns covered, counting both edges.
+    pub fn width(&self) -> u64 {
+        (self.max.x.to_i128() - self.min.x.to_i128() + 1) as u64
+    }
+
+    /// Number of rows covered, counting both edges.
+    pub fn height(&self) -> u64 {
+        (self.max.y.to_i128() - self.min.y.to_i128() + 1) as u64
+    }
+
+    /// Number of grid cells covered, counting both edges.
+    pub fn area(&self) -> u64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: &Vec2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// The overlapping part of both rectangles, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the signed area of a polygon given by its vertices in order (shoelace formula).
+/// Positive for counter-clockwise vertices in a y-up coordinate system.
+pub fn shoelace_doubled<T: Coord>(polygon: &[Vec2<T>]) -> i128 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.x.to_i128() * b.y.to_i128() - b.x.to_i128() * a.y.to_i128())
+        .sum()
+}
+
+/// The enclosed area of a polygon given by its vertices in order, with vertices as zero-sized
+/// points. Fractional areas are rounded down.
+pub fn polygon_area<T: Coord>(polygon: &[Vec2<T>]) -> u64 {
+    (shoelace_doubled(polygon).unsigned_abs() / 2) as u64
+}
+
+/// Number of lattice points on the boundary of a polygon with integer vertices.
+pub fn boundary_points<T: Coord>(polygon: &[Vec2<T>]) -> u64 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| {
+            let dx = (a.x.to_i128() - b.x.to_i128()).unsigned_abs();
+            let dy = (a.y.to_i128() - b.y.to_i128()).unsigned_abs();
+            gcd(dx, dy) as u64
+        })
+        .sum()
+}
+
+/// Number of lattice points strictly inside a polygon with integer vertices (Pick's theorem).
+pub fn interior_points<T: Coord>(polygon: &[Vec2<T>]) -> u64 {
+    let doubled = shoelace_doubled(polygon).unsigned_abs() as u64;
+    (doubled + 2 - boundary_points(polygon)) / 2
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Classifies `p` against a polygon given by its vertices in order.
+pub fn point_in_polygon<T: Coord>(p: &Vec2<T>, polygon: &[Vec2<T>]) -> Containment {
+    let p = (p.x.to_i128(), p.y.to_i128());
+    let vertices: Vec<_> = polygon
+        .iter()
+        .map(|v| (v.x.to_i128(), v.y.to_i128()))
+        .collect();
+    classify(p, &vertices)
+}
+
+/// Even-odd ray casting towards +x, with points on an edge reported as [`Containment::Boundary`].
+fn classify(p: (i128, i128), polygon: &[(i128, i128)]) -> Containment {
+    let mut inside = false;
+
+    for (&a, &b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+        let within_x = a.0.min(b.0) <= p.0 && p.0 <= a.0.max(b.0);
+        let within_y = a.1.min(b.1) <= p.1 && p.1 <= a.1.max(b.1);
+        if cross == 0 && within_x && within_y {
+            return Containment::Boundary;
+        }
+
+        // Half-open rule on y so that vertices on the ray are only counted once.
+        if (a.1 > p.1) != (b.1 > p.1) {
+            // x coordinate of the edge at height p.1 is a.0 + (p.1 - a.1) * (b.0 - a.0) / (b.1 - a.1);
+            // compare without dividing by moving the denominator to the other side.
+            let lhs = (p.0 - a.0) * (b.1 - a.1);
+            let rhs = (p.1 - a.1) * (b.0 - a.0);
+            if (b.1 > a.1 && lhs < rhs) || (b.1 < a.1 && lhs > rhs) {
+                inside = !inside;
+            }
+        }
+    }
+
+    if inside {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+/// Checks whether `rect` lies entirely within a rectilinear polygon (every edge horizontal or
+/// vertical), treating both as continuous regions and counting the polygon's boundary as inside.
+pub fn rect_in_rectilinear_polygon<T: Coord>(rect: &Rect<T>, polygon: &[Vec2<T>]) -> bool {
+    // Doubling every coordinate lets us sample midpoints between integer positions exactly.
+    let vertices: Vec<_> = polygon
+        .iter()
+        .map(|v| (v.x.to_i128() * 2, v.y.to_i128() * 2))
+        .collect();
+    let (x1, y1) = (rect.min.x.to_i128() * 2, rect.min.y.to_i128() * 2);
+    let (x2, y2) = (rect.max.x.to_i128() * 2, rect.max.y.to_i128() * 2);
+    let edges = || vertices.iter().zip(vertices.iter().cycle().skip(1));
+
+    if x1 < x2 && y1 < y2 {
+        // Without any edge passing through its open interior, the rectangle is either entirely
+        // inside or entirely outside, so checking its centre settles it.
+        let crosses = edges().any(|(&a, &b)| {
+            if a.1 == b.1 {
+                y1 < a.1 && a.1 < y2 && a.0.min(b.0) < x2 && a.0.max(b.0) > x1
+            } else {
+                x1 < a.0 && a.0 < x2 && a.1.min(b.1) < y2 && a.1.max(b.1) > y1
+            }
+        });
+        return !crosses
+            && classify(((x1 + x2) / 2, (y1 + y2) / 2), &vertices) == Containment::Inside;
+    }
+
+    // A degenerate rectangle is a segment (or a point). It can only change between inside and
+    // outside where an edge touches its line, so sample those positions and the midpoints between.
+    let horizontal = y1 == y2;
+    let (lo, hi) = if horizontal { (x1, x2) } else { (y1, y2) };
+    let mut breaks: Vec<i128> = vec![lo, hi];
+    for (&a, &b) in edges() {
+        for v in [a, b] {
+            let along = if horizontal { v.0 } else { v.1 };
+            if lo < along && along < hi {
+                breaks.push(along);
+            }
+        }
+    }
+    breaks.sort_unstable();
+    breaks.dedup();
+
+    let samples = breaks
+        .iter()
+        .copied()
+        .chain(breaks.windows(2).map(|w| (w[0] + w[1]) / 2));
+    samples
+        .map(|s| if horizontal { (s, y1) } else { (x1, s) })
+        .all(|p| classify(p, &vertices) != Containment::Outside)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps a sparse set of coordinates onto consecutive indices, preserving their order.
+#[derive(Debug, Clone)]
+pub struct Compressed<T> {
+    values: Vec<T>,
+}
+
+impl<T: Ord + Copy> Compressed<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// The compressed index of `value`, if it was part of the input.
+    pub fn index(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    /// The original value at compressed index `i`.
+    pub fn value(&self, i: usize) -> T {
+        self.values[i]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    // An L shape:
+    // (0,0)---(4,0)
+    //   |       |
+    //   |     (4,2)---(8,2)
+    //   |               |
+    // (0,4)-----------(8,4)
+    fn l_shape() -> Vec<Vec2<i64>> {
+        [(0, 0), (4, 0), (4, 2), (8, 2), (8, 4), (0, 4)]
+            .iter()
+            .map(|&(x, y)| Vec2::new(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec3::new(1_i64, 2, 3);
+        let b = Vec3::new(4_i64, -2, 3);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.dist_sq(&b), 25);
+        assert_eq!(Vec2::new(0_u8, 0).manhattan(&Vec2::new(3, 4)), 7);
+    }
+
+    #[test]
+    fn parses_vectors() {
+        assert_eq!("7, 3".parse::<Vec2<i32>>().unwrap(), Vec2::new(7, 3));
+        assert_eq!("1,2,3".parse::<Vec3<u64>>().unwrap(), Vec3::new(1, 2, 3));
+        assert!("1,2,3".parse::<Vec2<i32>>().is_err());
+        assert!("1,x".parse::<Vec2<i32>>().is_err());
+    }
+
+    #[test]
+    fn rectangles() {
+        let r = Rect::from_corners(Vec2::new(11, 1), Vec2::new(2, 5));
+        assert_eq!(r.area(), 50);
+        assert!(r.contains(&Vec2::new(2, 1)));
+        assert!(!r.contains(&Vec2::new(12, 1)));
+        let other = Rect::from_corners(Vec2::new(10, 5), Vec2::new(20, 20));
+        assert_eq!(r.intersection(&other).unwrap().area(), 2);
+        assert!(!r.intersects(&Rect::from_corners(Vec2::new(0, 6), Vec2::new(1, 9))));
+    }
+
+    #[test]
+    fn polygon_measurements() {
+        let poly = l_shape();
+        assert_eq!(polygon_area(&poly), 24);
+        assert_eq!(boundary_points(&poly), 24);
+        assert_eq!(interior_points(&poly), 13);
+    }
+
+    #[test]
+    fn containment() {
+        let poly = l_shape();
+        assert_eq!(
+            point_in_polygon(&Vec2::new(1, 1), &poly),
+            Containment::Inside
+        );
+        assert_eq!(
+            point_in_polygon(&Vec2::new(6, 2), &poly),
+            Containment::Boundary
+        );
+        assert_eq!(
+            point_in_polygon(&Vec2::new(0, 3), &poly),
+            Containment::Boundary
+        );
+        assert_eq!(
+            point_in_polygon(&Vec2::new(6, 1), &poly),
+            Containment::Outside
+        );
+        assert_eq!(
+            point_in_polygon(&Vec2::new(-1, 2), &poly),
+            Containment::Outside
+        );
+    }
+
+    #[test]
+    fn rectangles_in_polygon() {
+        let poly = l_shape();
+        let rect = |a: (i64, i64), b: (i64, i64)| {
+            Rect::from_corners(Vec2::new(a.0, a.1), Vec2::new(b.0, b.1))
+        };
+        assert!(rect_in_rectilinear_polygon(&rect((0, 0), (4, 4)), &poly));
+        assert!(rect_in_rectilinear_polygon(&rect((0, 2), (8, 4)), &poly));
+        assert!(!rect_in_rectilinear_polygon(&rect((0, 0), (8, 4)), &poly));
+        assert!(!rect_in_rectilinear_polygon(&rect((4, 0), (8, 2)), &poly));
+        // Degenerate rectangles along and across the boundary.
+        assert!(rect_in_rectilinear_polygon(&rect((4, 0), (4, 4)), &poly));
+        assert!(!rect_in_rectilinear_polygon(&rect((2, 1), (8, 1)), &poly));
+    }
+
+    #[test]
+    fn compression() {
+        let c = Compressed::new([100, 5, 30, 5]);
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.index(30), Some(1));
+        assert_eq!(c.index(31), None);
+        assert_eq!(c.value(2), 100);
+    }
+}
@@ -0,0 +1,476 @@
+//! Generic graph search helpers.
+//!
+//! Nodes are any `Clone + Eq + Hash` value (grid coordinates, points, indices, ...) and edges are
+//! described by a neighbour closure, so no graph structure has to be built up front.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A weighted edge between two node indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+}
+
+/// Rebuilds the path ending in `goal` by walking a predecessor map back to the start node.
+/// The returned path starts with the start node and ends with `goal`.
+pub fn reconstruct_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: &N) -> Vec<N> {
+    let mut path = vec![goal.clone()];
+    let mut current = goal;
+    while let Some(parent) = parents.get(current) {
+        path.push(parent.clone());
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search from `start`.
+/// Returns the number of steps to every reachable node and the predecessor of each node.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> (HashMap<N, usize>, HashMap<N, N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let d = dist[&node];
+        for next in neighbours(&node) {
+            if !dist.contains_key(&next) {
+                dist.insert(next.clone(), d + 1);
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (dist, parents)
+}
+
+/// Breadth-first search that stops at the first node satisfying `is_goal`.
+/// Returns the shortest path (by number of steps) including both ends.
+pub fn bfs_path<N, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, &node));
+        }
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Iterative depth-first search from `start`. Returns the nodes in visiting order.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        for next in neighbours(&node) {
+            if !seen.contains(&next) {
+                stack.push(next);
+            }
+        }
+        order.push(node);
+    }
+
+    order
+}
+
+/// Dijkstra's shortest paths from `start`. Edge weights must be non-negative.
+/// Returns the cost to every reachable node and the predecessor of each node.
+pub fn dijkstra<N, W, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> (HashMap<N, W>, HashMap<N, N>)
+where
+    N: Clone + Eq + Hash + Ord,
+    W: Copy + Ord + Default + Add<Output = W>,
+    I: IntoIterator<Item = (N, W)>,
+{
+    let mut dist = HashMap::from([(start.clone(), W::default())]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((W::default(), start))]);
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if dist.get(&node).is_some_and(|&d| cost > d) {
+            continue;
+        }
+        for (next, weight) in neighbours(&node) {
+            let next_cost = cost + weight;
+            if dist.get(&next).is_none_or(|&d| next_cost < d) {
+                dist.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    (dist, parents)
+}
+
+/// A* search from `start` to the first node satisfying `is_goal`.
+/// `heuristic` must never overestimate the remaining cost; a heuristic of zero is plain Dijkstra.
+/// Returns the path including both ends and its total cost.
+pub fn astar<N, W, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> W,
+) -> Option<(Vec<N>, W)>
+where
+    N: Clone + Eq + Hash + Ord,
+    W: Copy + Ord + Default + Add<Output = W>,
+    I: IntoIterator<Item = (N, W)>,
+{
+    let mut dist = HashMap::from([(start.clone(), W::default())]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), W::default(), start))]);
+
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, &node), cost));
+        }
+        if dist.get(&node).is_some_and(|&d| cost > d) {
+            continue;
+        }
+        for (next, weight) in neighbours(&node) {
+            let next_cost = cost + weight;
+            if dist.get(&next).is_none_or(|&d| next_cost < d) {
+                dist.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Splits `nodes` into connected components, following edges given by `neighbours`.
+/// Components are returned in the order their first node appears in `nodes`.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = dfs(node, &mut neighbours);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/// The 4-connected neighbours of `(row, col)` that lie within a `rows` x `cols` grid.
+pub fn orthogonal_neighbours(
+    (row, col): (usize, usize),
+    rows: usize,
+    cols: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    [
+        (row.wrapping_sub(1), col),
+        (row, col.wrapping_sub(1)),
+        (row, col + 1),
+        (row + 1, col),
+    ]
+    .into_iter()
+    .filter(move |&(r, c)| r < rows && c < cols)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A disjoint-set forest with union by size and path compression.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Creates `n` singleton sets, one for each index in `0..n`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    /// Returns the representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = x;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `false` if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        true
+    }
+
+    /// Number of elements in the set containing `x`.
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Sizes of every disjoint set, in no particular order.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        (0..self.parent.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|x| self.size[x])
+            .collect()
+    }
+}
+
+/// Kruskal's minimum spanning forest over nodes `0..n`.
+/// Edges are returned in the order they were added, i.e. by ascending weight.
+pub fn kruskal<W: Copy + Ord>(n: usize, mut edges: Vec<Edge<W>>) -> Vec<Edge<W>> {
+    edges.sort_unstable_by_key(|e| e.weight);
+    let mut sets = UnionFind::new(n);
+    edges
+        .into_iter()
+        .filter(|e| sets.union(e.from, e.to))
+        .take(n.saturating_sub(1))
+        .collect()
+}
+
+/// Prim's minimum spanning tree over a complete graph of nodes `0..n`, with the weight of each
+/// pair given by `weight`. Runs in O(n²) without materialising the edges, which suits dense
+/// point clouds. Edges are returned in the order they were added.
+pub fn prim<W: Copy + Ord>(n: usize, weight: impl Fn(usize, usize) -> W) -> Vec<Edge<W>> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut in_tree = vec![false; n];
+    // Cheapest known link from each node into the tree, as `(weight, tree node)`.
+    let mut links: Vec<Option<(W, usize)>> = (0..n).map(|i| Some((weight(0, i), 0))).collect();
+    let mut edges = Vec::with_capacity(n - 1);
+    in_tree[0] = true;
+
+    for _ in 1..n {
+        let Some((next, (w, parent))) = links
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| !in_tree[i])
+            .filter_map(|(i, link)| link.map(|l| (i, l)))
+            .min_by_key(|&(_, (w, _))| w)
+        else {
+            break;
+        };
+
+        in_tree[next] = true;
+        edges.push(Edge {
+            from: parent,
+            to: next,
+            weight: w,
+        });
+
+        for (i, link) in links.iter_mut().enumerate() {
+            if !in_tree[i] {
+                let w = weight(next, i);
+                if link.is_none_or(|(current, _)| w < current) {
+                    *link = Some((w, next));
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// The `k` lightest edges of the complete graph over nodes `0..n`, sorted by ascending weight.
+/// Uses a bounded max-heap, so only `k` edges are kept in memory at any time.
+pub fn k_lightest_edges<W: Copy + Ord>(
+    n: usize,
+    k: usize,
+    weight: impl Fn(usize, usize) -> W,
+) -> Vec<Edge<W>> {
+    let mut heap: BinaryHeap<(W, usize, usize)> = BinaryHeap::with_capacity(k + 1);
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let w = weight(i, j);
+            if heap.len() < k {
+                heap.push((w, i, j));
+            } else if heap.peek().is_some_and(|&(max, _, _)| w < max) {
+                heap.pop();
+                heap.push((w, i, j));
+            }
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(weight, from, to)| Edge { from, to, weight })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2    4 - 5
+    //     |
+    //     3
+    fn neighbours(n: &usize) -> Vec<usize> {
+        match n {
+            0 => vec![1],
+            1 => vec![0, 2, 3],
+            2 | 3 => vec![1],
+            4 => vec![5],
+            5 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_distances_and_path() {
+        let (dist, parents) = bfs(0, neighbours);
+        assert_eq!(dist[&3], 2);
+        assert!(!dist.contains_key(&4));
+        assert_eq!(reconstruct_path(&parents, &3), vec![0, 1, 3]);
+        assert_eq!(bfs_path(0, |&n| n == 2, neighbours), Some(vec![0, 1, 2]));
+        assert_eq!(bfs_path(0, |&n| n == 5, neighbours), None);
+    }
+
+    #[test]
+    fn dfs_visits_reachable_nodes() {
+        let mut order = dfs(1, neighbours);
+        order.sort_unstable();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn components() {
+        let components = connected_components(0..6, neighbours);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[1].len(), 2);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        // A 3x3 grid where moving into a cell costs its value.
+        let costs = [[1, 9, 1], [1, 9, 1], [1, 1, 1]];
+        let step = |&(r, c): &(usize, usize)| {
+            orthogonal_neighbours((r, c), 3, 3).map(|(r, c)| ((r, c), costs[r][c] as u32))
+        };
+
+        let (dist, parents) = dijkstra((0, 0), step);
+        assert_eq!(dist[&(0, 2)], 6);
+        assert_eq!(reconstruct_path(&parents, &(0, 2)).len(), 7);
+
+        let manhattan = |&(r, c): &(usize, usize)| (r.abs_diff(0) + c.abs_diff(2)) as u32;
+        let (path, cost) = astar((0, 0), |&n| n == (0, 2), step, manhattan).unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(0, 2)));
+    }
+
+    #[test]
+    fn union_find() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(1, 2));
+        assert!(!sets.union(0, 2));
+        assert_eq!(sets.set_size(2), 3);
+        let mut sizes = sets.set_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn prim_and_kruskal_agree() {
+        let points = [0_i64, 1, 5, 6, 20];
+        let weight = |a: usize, b: usize| points[a].abs_diff(points[b]);
+
+        let prim_tree = prim(points.len(), weight);
+        let all_edges = (0..points.len())
+            .flat_map(|from| ((from + 1)..points.len()).map(move |to| (from, to)))
+            .map(|(from, to)| Edge {
+                from,
+                to,
+                weight: weight(from, to),
+            })
+            .collect();
+        let kruskal_tree = kruskal(points.len(), all_edges);
+
+        let total = |tree: &[Edge<u64>]| tree.iter().map(|e| e.weight).sum::<u64>();
+        assert_eq!(prim_tree.len(), 4);
+        assert_eq!(total(&prim_tree), 20);
+        assert_eq!(total(&kruskal_tree), 20);
+        assert_eq!(kruskal_tree.last().unwrap().weight, 14);
+    }
+
+    #[test]
+    fn lightest_edges() {
+        let points = [0_i64, 10, 11, 13];
+        let edges = k_lightest_edges(points.len(), 2, |a, b| points[a].abs_diff(points[b]));
+        let weights: Vec<_> = edges.iter().map(|e| e.weight).collect();
+        assert_eq!(weights, vec![1, 2]);
+    }
+}
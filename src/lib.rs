@@ -1,3 +1,10 @@
+pub mod bitgrid;
+pub mod cycle;
+pub mod geometry;
+pub mod graph;
+pub mod math;
+pub mod property;
 pub mod template;
+pub mod viz;
 
 // Use this file to add helper functions and additional modules.
@@ -1,133 +1,574 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, asm, check, completions, download, generate, inputs, profile, read, scaffold, solve,
+    status, time, watch,
+};
+use advent_of_code::template::{ANSI_BOLD, ANSI_RESET, Day, cli};
 use args::{AppArguments, parse};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{
+        self, ArgsError, Command, ExistingPolicy, InputsAction, Shell, Visualize,
+    };
+    use advent_of_code::template::{Day, DaySelector, runner};
+    use advent_of_code::viz;
+    use std::ffi::OsString;
+    use std::fmt::Display;
+    use std::str::FromStr;
 
     pub enum AppArguments {
+        Help {
+            command: Option<&'static Command>,
+        },
+        Completions {
+            shell: Shell,
+        },
         Download {
-            day: Day,
+            days: Vec<Day>,
+            example: Option<usize>,
         },
         Read {
-            day: Day,
+            days: Vec<Day>,
         },
         Scaffold {
-            day: Day,
+            days: Vec<Day>,
             download: bool,
-            overwrite: bool,
+            existing: ExistingPolicy,
+            dry_run: bool,
+            template: Option<String>,
+            example: Option<usize>,
         },
         Solve {
-            day: Day,
+            days: Vec<Day>,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            each_input: bool,
+            visualize: Option<(Visualize, u32)>,
+            debug: bool,
+        },
+        Watch {
+            day: Day,
+            release: bool,
         },
         All {
+            days: Vec<Day>,
             release: bool,
+            in_process: bool,
+            input: Option<String>,
         },
         Time {
-            all: bool,
-            day: Option<Day>,
+            days: Vec<Day>,
             store: bool,
+            input: Option<String>,
+            each_input: bool,
+            sizes: Option<Vec<usize>>,
+            seed: u64,
+        },
+        Generate {
+            day: Day,
+            size: usize,
+            seed: u64,
+        },
+        Check {
+            day: Day,
+            size: usize,
+            seeds: u64,
+        },
+        Asm {
+            day: Day,
+            part: Option<u8>,
+            diff: bool,
+            llvm_ir: bool,
+        },
+        Profile {
+            day: Day,
+            part: Option<u8>,
+            iterations: Option<u64>,
+            flamegraph: bool,
+        },
+        Status {
+            days: Vec<Day>,
+            check: bool,
+        },
+        Inputs {
+            action: InputsAction,
+            file: Option<String>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// The arguments of a command. Flags are read by the name declared in [`cli::COMMANDS`], and
+    /// reading one the command does not declare panics in debug builds, so the help and the
+    /// completions cannot miss a flag that is parsed here.
+    struct Args {
+        command: &'static Command,
+        args: pico_args::Arguments,
+    }
+
+    impl Args {
+        fn declare(&self, flag: &str, takes_value: bool) {
+            debug_assert!(
+                self.command
+                    .flags
+                    .iter()
+                    .any(|f| f.name == flag && f.value.is_some() == takes_value),
+                "`{flag}` is parsed but not declared for `{}` in `cli::COMMANDS`",
+                self.command.name
+            );
+        }
+
+        fn contains(&mut self, flag: &'static str) -> bool {
+            self.declare(flag, false);
+            self.args.contains(flag)
+        }
+
+        fn opt_value_from_str<T>(&mut self, flag: &'static str) -> Result<Option<T>, ArgsError>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            self.declare(flag, true);
+            Ok(self.args.opt_value_from_str(flag)?)
+        }
+    }
+
+    /// The next positional argument. Flags are parsed before positionals, so one that is left
+    /// over at this point is unknown rather than a value.
+    fn positional(args: &mut pico_args::Arguments) -> Result<Option<String>, ArgsError> {
+        let value: Option<String> = args.opt_free_from_str()?;
+        match value {
+            Some(value) if value.starts_with('-') => Err(ArgsError::UnknownFlags(vec![value])),
+            value => Ok(value),
+        }
+    }
+
+    fn days(args: &mut Args) -> Result<Vec<Day>, ArgsError> {
+        let value: String =
+            positional(&mut args.args)?.ok_or(ArgsError::MissingArgument("<days>"))?;
+        Ok(cli::parse_days(&value)?.resolve())
+    }
+
+    fn single_day(args: &mut Args) -> Result<Day, ArgsError> {
+        match days(args)?.as_slice() {
+            [day] => Ok(*day),
+            days => Err(ArgsError::InvalidValue {
+                name: "<day>",
+                value: format!("{} days", days.len()),
+                expected: "a single day",
+            }),
+        }
+    }
+
+    fn opt_days(args: &mut Args, default: DaySelector) -> Result<Vec<Day>, ArgsError> {
+        let value = positional(&mut args.args)?;
+        let selector = value.as_deref().map(cli::parse_days).transpose()?;
+        Ok(selector.unwrap_or(default).resolve())
+    }
+
+    fn part(args: &mut Args, flag: &'static str) -> Result<Option<u8>, ArgsError> {
+        let value: Option<String> = args.opt_value_from_str(flag)?;
+        value.map(|value| parse_part(flag, value)).transpose()
+    }
+
+    /// An optional part given as a positional argument, e.g. `asm 3 2`.
+    fn opt_part(args: &mut Args) -> Result<Option<u8>, ArgsError> {
+        let value = positional(&mut args.args)?;
+        value.map(|value| parse_part("[part]", value)).transpose()
+    }
+
+    fn parse_part(name: &'static str, value: String) -> Result<u8, ArgsError> {
+        match value.as_str() {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err(ArgsError::InvalidValue {
+                name,
+                value,
+                expected: "1 or 2",
+            }),
+        }
+    }
+
+    fn example(args: &mut Args) -> Result<Option<usize>, ArgsError> {
+        let value: Option<String> = args.opt_value_from_str("--example")?;
+        value
+            .map(|value| match value.parse() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(ArgsError::InvalidValue {
+                    name: "--example",
+                    value,
+                    expected: "a code block number starting at 1",
+                }),
+            })
+            .transpose()
+    }
+
+    fn number<T: std::str::FromStr>(
+        args: &mut Args,
+        flag: &'static str,
+        default: T,
+    ) -> Result<T, ArgsError> {
+        Ok(opt_number(args, flag)?.unwrap_or(default))
+    }
+
+    fn opt_number<T: std::str::FromStr>(
+        args: &mut Args,
+        flag: &'static str,
+    ) -> Result<Option<T>, ArgsError> {
+        let value: Option<String> = args.opt_value_from_str(flag)?;
+        value
+            .map(|value| {
+                value.parse().map_err(|_| ArgsError::InvalidValue {
+                    name: flag,
+                    value,
+                    expected: "a positive number",
+                })
+            })
+            .transpose()
+    }
+
+    fn sizes(args: &mut Args) -> Result<Option<Vec<usize>>, ArgsError> {
+        let value: Option<String> = args.opt_value_from_str("--sizes")?;
+        value
+            .map(|value| {
+                let sizes: Option<Vec<usize>> = value
+                    .split(',')
+                    .map(|size| size.trim().parse().ok().filter(|&size| size > 0))
+                    .collect();
+                sizes.ok_or(ArgsError::InvalidValue {
+                    name: "--sizes",
+                    value,
+                    expected: "a list of sizes like 1000,2000,4000",
+                })
+            })
+            .transpose()
+    }
+
+    /// Fails if both flags are given.
+    fn exclusive(a: (bool, &'static str), b: (bool, &'static str)) -> Result<(), ArgsError> {
+        if a.0 && b.0 {
+            return Err(ArgsError::Conflict(a.1, b.1));
+        }
+        Ok(())
+    }
+
+    pub fn parse() -> Result<AppArguments, ArgsError> {
+        parse_from(std::env::args_os().skip(1).collect())
+    }
+
+    pub fn parse_from(args: Vec<OsString>) -> Result<AppArguments, ArgsError> {
+        let mut args = pico_args::Arguments::from_vec(args);
+
+        let Some(name) = args.subcommand()? else {
+            if args.contains(["-h", "--help"]) {
+                cli::check_remaining(args.finish())?;
+                return Ok(AppArguments::Help { command: None });
+            }
+            return Err(ArgsError::NoCommand);
+        };
+
+        if name == "help" {
+            let topic = positional(&mut args)?;
+            cli::check_remaining(args.finish())?;
+            let command = topic
+                .map(|topic| cli::find_command(&topic).ok_or(ArgsError::UnknownCommand(topic)))
+                .transpose()?;
+            return Ok(AppArguments::Help { command });
+        }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let command = cli::find_command(&name).ok_or(ArgsError::UnknownCommand(name))?;
+
+        if args.contains(["-h", "--help"]) {
+            return Ok(AppArguments::Help {
+                command: Some(command),
+            });
+        }
+        let mut args = Args { command, args };
+
+        let app_args = match command.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                in_process: args.contains("--in-process"),
+                input: args.opt_value_from_str("--input")?,
+                days: opt_days(&mut args, DaySelector::all())?,
             },
-            Some("time") => {
-                let all = args.contains("--all");
+            "time" => {
+                // without a selection, only bench days that are not fully benched yet.
+                let default = if args.contains("--all") {
+                    DaySelector::all()
+                } else {
+                    DaySelector::unsolved()
+                };
                 let store = args.contains("--store");
-
+                let input: Option<String> = args.opt_value_from_str("--input")?;
+                let each_input = args.contains("--each-input");
+                let sizes = sizes(&mut args)?;
+                let seed = number(&mut args, "--seed", runner::DEFAULT_SEED)?;
+                exclusive((input.is_some(), "--input"), (each_input, "--each-input"))?;
+                exclusive((store, "--store"), (each_input, "--each-input"))?;
+                for (set, flag) in [
+                    (store, "--store"),
+                    (input.is_some(), "--input"),
+                    (each_input, "--each-input"),
+                ] {
+                    exclusive((sizes.is_some(), "--sizes"), (set, flag))?;
+                }
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
                     store,
+                    input,
+                    each_input,
+                    sizes,
+                    seed,
+                    days: opt_days(&mut args, default)?,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "check" => AppArguments::Check {
+                size: number(&mut args, "--size", runner::DEFAULT_SIZE)?,
+                seeds: number(&mut args, "--seeds", 3)?,
+                day: single_day(&mut args)?,
+            },
+            "gen" => AppArguments::Generate {
+                size: number(&mut args, "--size", runner::DEFAULT_SIZE)?,
+                seed: number(&mut args, "--seed", runner::DEFAULT_SEED)?,
+                day: single_day(&mut args)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "asm" => AppArguments::Asm {
+                diff: args.contains("--diff"),
+                llvm_ir: args.contains("--llvm-ir"),
+                day: single_day(&mut args)?,
+                part: opt_part(&mut args)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            "profile" => AppArguments::Profile {
+                part: part(&mut args, "--part")?,
+                iterations: opt_number(&mut args, "--iterations")?,
+                flamegraph: args.contains("--flamegraph"),
+                day: single_day(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            "status" => AppArguments::Status {
+                check: args.contains("--check"),
+                days: opt_days(&mut args, DaySelector::all())?,
+            },
+            "download" => AppArguments::Download {
+                example: example(&mut args)?,
+                days: days(&mut args)?,
+            },
+            "read" => AppArguments::Read {
+                days: days(&mut args)?,
+            },
+            "scaffold" => {
+                let mut existing = ExistingPolicy::default();
+                if args.contains("--overwrite") {
+                    existing.apply("module=overwrite")?;
+                }
+                if let Some(spec) = args.opt_value_from_str::<String>("--existing")? {
+                    existing.apply(&spec)?;
+                }
+                AppArguments::Scaffold {
+                    download: args.contains("--download"),
+                    existing,
+                    dry_run: args.contains("--dry-run"),
+                    template: args.opt_value_from_str("--template")?,
+                    example: example(&mut args)?,
+                    days: days(&mut args)?,
+                }
+            }
+            "solve" => {
+                let release = args.contains("--release");
+                let submit = part(&mut args, "--submit")?;
+                let dhat = args.contains("--dhat");
+                let input: Option<String> = args.opt_value_from_str("--input")?;
+                let each_input = args.contains("--each-input");
+                let visualize: Option<String> = args.opt_value_from_str("--visualize")?;
+                let visualize = visualize.map(|mode| mode.parse()).transpose()?;
+                let fps = number(&mut args, "--fps", viz::DEFAULT_FPS)?;
+                let debug = args.contains("--debug");
+                exclusive((input.is_some(), "--input"), (each_input, "--each-input"))?;
+                exclusive((submit.is_some(), "--submit"), (each_input, "--each-input"))?;
+                exclusive((dhat, "--dhat"), (each_input, "--each-input"))?;
+                // frames are only recorded in debug builds.
+                for (set, flag) in [
+                    (release, "--release"),
+                    (dhat, "--dhat"),
+                    (each_input, "--each-input"),
+                ] {
+                    exclusive((visualize.is_some(), "--visualize"), (set, flag))?;
+                }
+                let days = days(&mut args)?;
+                if submit.is_some() && days.len() != 1 {
+                    return Err(ArgsError::InvalidValue {
+                        name: "<days>",
+                        value: format!("{} days", days.len()),
+                        expected: "a single day when submitting",
+                    });
+                }
+                AppArguments::Solve {
+                    days,
+                    release,
+                    dhat,
+                    submit,
+                    input,
+                    each_input,
+                    visualize: visualize.map(|mode| (mode, fps)),
+                    debug,
+                }
+            }
+            "watch" => AppArguments::Watch {
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                day: single_day(&mut args)?,
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "inputs" => {
+                let file = args.opt_value_from_str("--file")?;
+                let action: String =
+                    positional(&mut args.args)?.ok_or(ArgsError::MissingArgument("<action>"))?;
+                AppArguments::Inputs {
+                    action: action.parse()?,
+                    file,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "completions" => {
+                let shell: String =
+                    positional(&mut args.args)?.ok_or(ArgsError::MissingArgument("<shell>"))?;
+                AppArguments::Completions {
+                    shell: shell.parse()?,
+                }
             }
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today,
+            _ => unreachable!("command `{}` is declared but not parsed", command.name),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+        cli::check_remaining(args.args.finish())?;
 
         Ok(app_args)
     }
 }
 
+/// Runs `f` for every selected day, with a header per day when there is more than one.
+fn for_each_day(days: &[Day], mut f: impl FnMut(Day)) {
+    if days.is_empty() {
+        eprintln!("No days selected.");
+    }
+    for (i, &day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+        }
+        f(day);
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
+            eprintln!("Run with `--help` for usage.");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::Help { command } => match command {
+                Some(command) => print!("{}", command.help()),
+                None => print!("{}", cli::help()),
+            },
+            AppArguments::Completions { shell } => completions::handle(shell),
+            AppArguments::Watch { day, release } => watch::handle(day, release),
+            AppArguments::All {
+                days,
+                release,
+                in_process,
+                input,
+            } => all::handle(&days, release, in_process, input.as_deref()),
+            AppArguments::Time {
+                days,
+                store,
+                input,
+                each_input,
+                sizes,
+                seed,
+            } => time::handle(
+                &days,
+                store,
+                input.as_deref(),
+                each_input,
+                sizes.as_deref(),
+                seed,
+            ),
+            AppArguments::Generate { day, size, seed } => generate::handle(day, size, seed),
+            AppArguments::Check { day, size, seeds } => check::handle(day, size, seeds),
+            AppArguments::Asm {
                 day,
+                part,
+                diff,
+                llvm_ir,
+            } => asm::handle(day, part, diff, llvm_ir),
+            AppArguments::Profile {
+                day,
+                part,
+                iterations,
+                flamegraph,
+            } => profile::handle(day, part, iterations, flamegraph),
+            AppArguments::Status { days, check } => status::handle(&days, check),
+            AppArguments::Inputs { action, file } => {
+                inputs::handle(action, file.as_deref().unwrap_or(inputs::DEFAULT_PACK_PATH))
+            }
+            AppArguments::Download { days, example } => {
+                for_each_day(&days, |day| download::handle(day, example));
+            }
+            AppArguments::Read { days } => for_each_day(&days, read::handle),
+            AppArguments::Scaffold {
+                days,
                 download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                existing,
+                dry_run,
+                template,
+                example,
+            } => for_each_day(&days, |day| {
+                // download first, so the puzzle can fill in the example and its answer.
+                if download && dry_run {
+                    println!("Would download input and puzzle for day {day}");
+                } else if download {
+                    download::fetch(day);
                 }
-            }
+                scaffold::handle(day, existing, template.as_deref(), example, dry_run);
+            }),
             AppArguments::Solve {
-                day,
+                days,
+                release,
+                each_input: true,
+                ..
+            } => solve::handle_each_input(&days, release),
+            AppArguments::Solve {
+                days,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                each_input: false,
+                visualize,
+                debug,
+            } => for_each_day(&days, |day| {
+                solve::handle(
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    input.as_deref(),
+                    visualize,
+                    debug,
+                );
+            }),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        download::fetch(day);
+                        scaffold::handle(day, Default::default(), None, None, false);
                         read::handle(day)
                     }
                     None => {
@@ -142,3 +583,49 @@ fn main() {
         },
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::args::parse_from;
+    use advent_of_code::template::cli::{COMMANDS, Flag, Positional};
+
+    /// A valid value for a flag.
+    fn sample(flag: &Flag) -> Option<&'static str> {
+        let placeholder = flag.value?;
+        Some(flag.choices.first().copied().unwrap_or(match placeholder {
+            "<part>" => "1",
+            "<list>" => "1000,2000",
+            "<n>" | "<seed>" => "2",
+            _ => "name",
+        }))
+    }
+
+    // reading a flag that is not declared panics, so this also checks the reverse: that every
+    // flag the parser accepts is in `COMMANDS`.
+    #[test]
+    fn parses_every_declared_flag() {
+        for command in COMMANDS {
+            let positional: &[&str] = match command.positional {
+                Positional::None | Positional::OptionalDays => &[],
+                Positional::Day | Positional::DayAndPart | Positional::Days => &["3"],
+                Positional::Shell => &["bash"],
+                Positional::InputsAction => &["pack"],
+            };
+            let invocations = std::iter::once(vec![]).chain(
+                command
+                    .flags
+                    .iter()
+                    .map(|flag| std::iter::once(flag.name).chain(sample(flag)).collect()),
+            );
+            for flags in invocations {
+                let args: Vec<&str> = std::iter::once(command.name)
+                    .chain(flags)
+                    .chain(positional.iter().copied())
+                    .collect();
+                if let Err(e) = parse_from(args.iter().map(Into::into).collect()) {
+                    panic!("`{}` was rejected: {e}", args.join(" "));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Exact integer number theory helpers.
+//!
+//! Everything here avoids floating point, so results stay correct across the whole `u64` / `i64`
+//! range. Functions that can overflow have a `checked_` variant returning [`None`] instead.
+
+/// Number of decimal digits in `n`. Zero has one digit.
+pub const fn digit_count(n: u64) -> u32 {
+    match n.checked_ilog10() {
+        Some(log) => log + 1,
+        None => 1,
+    }
+}
+
+/// `10^exp`, or [`None`] if it does not fit into a [`u64`].
+pub const fn checked_pow10(exp: u32) -> Option<u64> {
+    10u64.checked_pow(exp)
+}
+
+/// `10^exp`. Panics if the result does not fit into a [`u64`].
+pub const fn pow10(exp: u32) -> u64 {
+    match checked_pow10(exp) {
+        Some(x) => x,
+        None => panic!("power of ten does not fit into u64"),
+    }
+}
+
+/// Division rounding towards negative infinity.
+pub const fn div_floor(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// Division rounding towards positive infinity.
+pub const fn div_ceil(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) == (b < 0) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// Greatest common divisor. `gcd(0, 0)` is zero.
+pub const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or [`None`] if it does not fit into a [`u64`].
+pub const fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple. Panics if the result does not fit into a [`u64`].
+pub const fn lcm(a: u64, b: u64) -> u64 {
+    match checked_lcm(a, b) {
+        Some(x) => x,
+        None => panic!("lcm does not fit into u64"),
+    }
+}
+
+/// Extended Euclidean algorithm.
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the non-negative gcd.
+pub const fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// The inverse of `a` modulo `m` in `0..m`, or [`None`] if `a` and `m` are not coprime.
+pub const fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g == 1 { Some(x.rem_euclid(m)) } else { None }
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the chinese remainder theorem.
+/// Moduli do not need to be coprime. Returns `(x, lcm of moduli)` with `x` in `0..lcm`, or
+/// [`None`] if the system has no solution or the combined modulus overflows.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for &(residue, modulus) in congruences {
+        let (r, n) = (i128::from(residue), i128::from(modulus));
+        let (g, p, _) = extended_gcd(i64::try_from(m).ok()?, modulus);
+        let g = i128::from(g);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        let step = n / g;
+        let k = ((r - x) / g % step * i128::from(p)).rem_euclid(step);
+        x += m * k;
+        m *= step;
+        x = x.rem_euclid(m);
+    }
+
+    Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?))
+}
+
+/// The multiplier that repeats a block of `block_digits` digits `repetitions` times, e.g.
+/// `(1, 2) => 11`, `(2, 2) => 101`, `(2, 3) => 10101`. Multiplying a block by it yields the
+/// repeated number: `12 * 10101 = 121212`. Returns [`None`] if it does not fit into a [`u64`].
+pub const fn checked_repeated_pattern(block_digits: u32, repetitions: u32) -> Option<u64> {
+    let Some(shift) = checked_pow10(block_digits) else {
+        return None;
+    };
+    let mut pattern: u64 = 0;
+    let mut i = 0;
+    while i < repetitions {
+        pattern = match pattern.checked_mul(shift) {
+            Some(p) => p + 1,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(pattern)
+}
+
+/// See [`checked_repeated_pattern`]. Panics if the result does not fit into a [`u64`].
+pub const fn repeated_pattern(block_digits: u32, repetitions: u32) -> u64 {
+    match checked_repeated_pattern(block_digits, repetitions) {
+        Some(x) => x,
+        None => panic!("repeated pattern does not fit into u64"),
+    }
+}
+
+/// Sum of all integers in `first..=last`, or [`None`] on overflow. Empty ranges sum to zero.
+pub const fn checked_arithmetic_sum(first: u64, last: u64) -> Option<u64> {
+    if first > last {
+        return Some(0);
+    }
+    let count = (last - first) as u128 + 1;
+    let sum = count * (first as u128 + last as u128) / 2;
+    if sum > u64::MAX as u128 {
+        None
+    } else {
+        Some(sum as u64)
+    }
+}
+
+/// Sum of all integers in `first..=last`. Panics if the result does not fit into a [`u64`].
+pub const fn arithmetic_sum(first: u64, last: u64) -> u64 {
+    match checked_arithmetic_sum(first, last) {
+        Some(x) => x,
+        None => panic!("arithmetic sum does not fit into u64"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_and_powers() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(999_999_999_999_999_999), 18);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(pow10(19), 10_000_000_000_000_000_000);
+        assert_eq!(checked_pow10(20), None);
+    }
+
+    #[test]
+    fn rounding_division() {
+        assert_eq!(div_floor(7, 2), 3);
+        assert_eq!(div_floor(-7, 2), -4);
+        assert_eq!(div_floor(7, -2), -4);
+        assert_eq!(div_floor(-8, 2), -4);
+        assert_eq!(div_ceil(7, 2), 4);
+        assert_eq!(div_ceil(-7, 2), -3);
+        assert_eq!(div_ceil(-7, -2), 4);
+        assert_eq!(div_ceil(8, 2), 4);
+    }
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn inverses_and_crt() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn repeated_patterns() {
+        assert_eq!(repeated_pattern(1, 2), 11);
+        assert_eq!(repeated_pattern(2, 2), 101);
+        assert_eq!(repeated_pattern(2, 3), 10101);
+        assert_eq!(repeated_pattern(3, 1), 1);
+        assert_eq!(repeated_pattern(1, 19), 1_111_111_111_111_111_111);
+        assert_eq!(checked_repeated_pattern(1, 21), None);
+        assert_eq!(checked_repeated_pattern(10, 2), Some(10_000_000_001));
+        assert_eq!(checked_repeated_pattern(10, 3), None);
+    }
+
+    #[test]
+    fn series() {
+        assert_eq!(arithmetic_sum(1, 100), 5050);
+        assert_eq!(arithmetic_sum(10, 9), 0);
+        assert_eq!(arithmetic_sum(u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(checked_arithmetic_sum(0, u64::MAX), None);
+    }
+}
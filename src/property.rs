@@ -0,0 +1,169 @@
+//! Property testing for solutions: run an invariant on many random inputs and, when it fails,
+//! shrink the input line by line to a small counterexample that is easier to debug.
+//!
+//! Inputs come from a seeded [`Rng`], usually through the generator of the day, so a failure can
+//! be replayed by setting [`SEED_VAR`] to the seed in the failure message.
+use std::env;
+
+use crate::template::rng::Rng;
+
+/// Number of random inputs a property is checked on, unless given otherwise.
+pub const DEFAULT_CASES: u64 = 100;
+
+/// Environment variable that replays a single case, e.g. `AOC_PROPERTY_SEED=17 cargo test`.
+pub const SEED_VAR: &str = "AOC_PROPERTY_SEED";
+
+/// Salt for the generator passed to the property, so it is independent of the one that generated
+/// the input but still the same on every call with a seed.
+const PROPERTY_SALT: u64 = 0x5eed_5eed_5eed_5eed;
+
+/// Checks that `property` holds for the inputs generated with seeds `1..=cases`.
+///
+/// The property gets the input and its own seeded [`Rng`], e.g. for shuffling the input. It
+/// should return `false` rather than panic, so that a failing input can be shrunk.
+///
+/// # Panics
+/// Panics with the seed and the shrunk input if the property does not hold for a case.
+pub fn check_lines(
+    cases: u64,
+    generate: impl Fn(&mut Rng) -> String,
+    property: impl Fn(&str, &mut Rng) -> bool,
+) {
+    let seeds: Vec<u64> = match env::var(SEED_VAR).ok().and_then(|s| s.parse().ok()) {
+        Some(seed) => vec![seed],
+        None => (1..=cases).collect(),
+    };
+
+    for seed in seeds {
+        let holds = |input: &str| property(input, &mut Rng::new(seed ^ PROPERTY_SALT));
+        let input = generate(&mut Rng::new(seed));
+        if holds(&input) {
+            continue;
+        }
+
+        let shrunk = shrink_lines(&input, |input| !holds(input));
+        panic!(
+            "property does not hold for seed {seed}, replay it with {SEED_VAR}={seed}.\n\
+             Shrunk from {} to {} lines:\n{shrunk}",
+            input.lines().count(),
+            shrunk.lines().count(),
+        );
+    }
+}
+
+/// Number of sections separated by blank lines.
+fn section_count(lines: &[&str]) -> usize {
+    lines
+        .split(|line| line.is_empty())
+        .filter(|section| !section.is_empty())
+        .count()
+}
+
+/// Removes as many lines from `input` as possible while `fails` still returns `true`, first in
+/// large chunks and then one by one. Blank lines are kept and every section keeps at least one
+/// line, so inputs made of several sections separated by blank lines keep their shape.
+pub fn shrink_lines(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let trailing_newline = if input.ends_with('\n') { "\n" } else { "" };
+    let join = |lines: &[&str]| lines.join("\n") + trailing_newline;
+
+    let mut lines: Vec<&str> = input.lines().collect();
+    let sections = section_count(&lines);
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed_any = false;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = lines[..start]
+                .iter()
+                .chain(lines[start..end].iter().filter(|line| line.is_empty()))
+                .chain(&lines[end..])
+                .copied()
+                .collect();
+
+            if candidate.len() < lines.len()
+                && section_count(&candidate) == sections
+                && fails(&join(&candidate))
+            {
+                lines = candidate;
+                removed_any = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+    join(&lines)
+}
+
+/// Shuffles the lines within each section of `input`, where sections are separated by blank
+/// lines. For properties like "the order of the lines does not matter".
+pub fn shuffle_lines(input: &str, rng: &mut Rng) -> String {
+    let trailing_newline = if input.ends_with('\n') { "\n" } else { "" };
+    let sections: Vec<String> = input
+        .trim_end_matches('\n')
+        .split("\n\n")
+        .map(|section| {
+            let mut lines: Vec<&str> = section.lines().collect();
+            rng.shuffle(&mut lines);
+            lines.join("\n")
+        })
+        .collect();
+    sections.join("\n\n") + trailing_newline
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_the_failing_lines() {
+        let input = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let fails =
+            |input: &str| input.lines().any(|l| l == "7") && input.lines().any(|l| l == "3");
+        assert_eq!(shrink_lines(input, fails), "3\n7\n");
+        assert_eq!(shrink_lines(input, |_| true).lines().count(), 1);
+        assert_eq!(shrink_lines("a\nb", |input| input.contains('b')), "b");
+    }
+
+    #[test]
+    fn keeps_sections_when_shrinking() {
+        let input = "1-2\n3-4\n5-6\n\n1\n2\n3\n";
+        let fails = |input: &str| input.contains("3-4") && input.contains("\n2\n");
+        assert_eq!(shrink_lines(input, fails), "3-4\n\n2\n");
+        assert_eq!(shrink_lines(input, |_| true).lines().count(), 3);
+    }
+
+    #[test]
+    fn shuffles_within_sections() {
+        let input = "a\nb\nc\nd\n\n1\n2\n3\n4\n";
+        let shuffled = shuffle_lines(input, &mut Rng::new(4));
+        assert_ne!(shuffled, input);
+
+        let (ranges, items) = shuffled.split_once("\n\n").unwrap();
+        let mut ranges: Vec<&str> = ranges.lines().collect();
+        let mut items: Vec<&str> = items.lines().collect();
+        ranges.sort_unstable();
+        items.sort_unstable();
+        assert_eq!(ranges, ["a", "b", "c", "d"]);
+        assert_eq!(items, ["1", "2", "3", "4"]);
+        assert!(shuffled.ends_with("\n"));
+    }
+
+    #[test]
+    fn checks_properties() {
+        let generate = |rng: &mut Rng| format!("{}\n{}\n", rng.below(100), rng.below(100));
+        check_lines(20, generate, |input, rng| {
+            shuffle_lines(input, rng).lines().count() == input.lines().count()
+        });
+
+        let result = std::panic::catch_unwind(|| {
+            check_lines(20, generate, |input, _| input.lines().count() < 2);
+        });
+        assert!(result.is_err());
+    }
+}
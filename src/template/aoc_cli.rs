@@ -4,7 +4,8 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::puzzle::get_puzzle_path;
+use crate::template::{Day, encryption};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -66,6 +67,14 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     );
 
     let output = call_aoc_cli(&args)?;
+
+    // aoc-cli writes plain files, encrypt them if a key is configured.
+    for path in [&input_path, &puzzle_path] {
+        if let Err(e) = encryption::seal(path) {
+            eprintln!("Failed to encrypt \"{path}\": {e}");
+        }
+    }
+
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
@@ -84,11 +93,8 @@ fn get_input_path(day: Day) -> String {
     format!("data/inputs/{day}.txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
+/// The year configured via the `AOC_YEAR` environment variable.
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -0,0 +1,779 @@
+/// Declarative description of the command-line interface.
+/// The help output and the shell completion scripts are generated from [`COMMANDS`]. The parser
+/// in `main.rs` is written by hand, its tests check that it accepts exactly the flags declared
+/// here, so a new flag is added in both places.
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET, DaySelector, DaySelectorError};
+
+/// Name of the binary the completion scripts are registered for.
+pub const BIN_NAME: &str = "advent_of_code";
+
+/// The positional argument a command accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Positional {
+    None,
+    Day,
+    /// A single day, optionally followed by a part.
+    DayAndPart,
+    Days,
+    OptionalDays,
+    Shell,
+    InputsAction,
+}
+
+/// A `--flag`, optionally taking a value.
+#[derive(Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    /// Placeholder for the flag's value, e.g. `<part>`. Flags without one are switches.
+    pub value: Option<&'static str>,
+    /// Values offered by shell completion.
+    pub choices: &'static [&'static str],
+    /// Whether the value is a path, so shell completion offers file names.
+    pub path: bool,
+    pub help: &'static str,
+}
+
+/// A subcommand such as `solve` or `time`.
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub positional: Positional,
+    pub flags: &'static [Flag],
+}
+
+const fn switch(name: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: None,
+        choices: &[],
+        path: false,
+        help,
+    }
+}
+
+pub static COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution module, input and example files for a day",
+        positional: Positional::Days,
+        flags: &[
+            switch("--download", "Download the input and puzzle afterwards"),
+            switch("--overwrite", "Same as --existing module=overwrite"),
+            Flag {
+                name: "--existing",
+                value: Some("<policy>"),
+                choices: &["skip", "overwrite", "backup"],
+                path: false,
+                help: "Handle existing files with skip, overwrite or backup, e.g. backup,example=skip",
+            },
+            switch(
+                "--dry-run",
+                "Only print what would be created, skipped or replaced",
+            ),
+            Flag {
+                name: "--template",
+                value: Some("<name>"),
+                choices: &[],
+                path: false,
+                help: "Use templates/<name>.txt instead of the built-in template",
+            },
+            Flag {
+                name: "--example",
+                value: Some("<n>"),
+                choices: &[],
+                path: false,
+                help: "Use the n-th code block of the puzzle as example",
+            },
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Download the input, puzzle description and example for a day via aoc-cli",
+        positional: Positional::Days,
+        flags: &[Flag {
+            name: "--example",
+            value: Some("<n>"),
+            choices: &[],
+            path: false,
+            help: "Use the n-th code block of the puzzle as example",
+        }],
+    },
+    Command {
+        name: "read",
+        about: "Print the puzzle description for a day via aoc-cli",
+        positional: Positional::Days,
+        flags: &[],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution for a day against its input",
+        positional: Positional::Days,
+        flags: &[
+            switch("--release", "Build with optimisations"),
+            switch("--dhat", "Profile heap allocations with dhat"),
+            Flag {
+                name: "--submit",
+                value: Some("<part>"),
+                choices: &["1", "2"],
+                path: false,
+                help: "Submit the answer for part 1 or 2 via aoc-cli",
+            },
+            Flag {
+                name: "--input",
+                value: Some("<name|path>"),
+                choices: &[],
+                path: true,
+                help: "Run against data/inputs/<day>/<name>.txt or a file instead of the default input",
+            },
+            switch(
+                "--each-input",
+                "Run against every input of the day and compare the answers",
+            ),
+            Flag {
+                name: "--visualize",
+                value: Some("<mode>"),
+                choices: &["terminal", "frames"],
+                path: false,
+                help: "Play the frames recorded with viz in the terminal, or write them to data/frames",
+            },
+            Flag {
+                name: "--fps",
+                value: Some("<n>"),
+                choices: &[],
+                path: false,
+                help: "Frames per second of the terminal playback, defaults to 10",
+            },
+            switch("--debug", "Print the messages of debug! to stderr"),
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run every solution, or the selected days",
+        positional: Positional::OptionalDays,
+        flags: &[
+            switch("--release", "Build with optimisations"),
+            switch(
+                "--in-process",
+                "Run all days from a single binary and report the total time",
+            ),
+            Flag {
+                name: "--input",
+                value: Some("<name|path>"),
+                choices: &[],
+                path: true,
+                help: "Run against data/inputs/<day>/<name>.txt or a file instead of the default input",
+            },
+        ],
+    },
+    Command {
+        name: "time",
+        about: "Benchmark solutions, by default only those without stored timings",
+        positional: Positional::OptionalDays,
+        flags: &[
+            switch(
+                "--all",
+                "Benchmark every solution, same as `all` for [days]",
+            ),
+            switch("--store", "Store the timings in the readme"),
+            Flag {
+                name: "--input",
+                value: Some("<name|path>"),
+                choices: &[],
+                path: true,
+                help: "Run against data/inputs/<day>/<name>.txt or a file instead of the default input",
+            },
+            switch(
+                "--each-input",
+                "Bench every input of the day and compare the times",
+            ),
+            Flag {
+                name: "--sizes",
+                value: Some("<list>"),
+                choices: &[],
+                path: false,
+                help: "Bench generated inputs of each size, e.g. 1000,2000,4000, and show the growth",
+            },
+            Flag {
+                name: "--seed",
+                value: Some("<seed>"),
+                choices: &[],
+                path: false,
+                help: "Seed for the generated inputs of --sizes, defaults to 1",
+            },
+        ],
+    },
+    Command {
+        name: "check",
+        about: "Compare the variants of a day's parts on its examples, input and generated inputs",
+        positional: Positional::Day,
+        flags: &[
+            Flag {
+                name: "--size",
+                value: Some("<n>"),
+                choices: &[],
+                path: false,
+                help: "Size of the generated inputs, defaults to 1000",
+            },
+            Flag {
+                name: "--seeds",
+                value: Some("<n>"),
+                choices: &[],
+                path: false,
+                help: "Number of generated inputs, with seeds 1 to n, defaults to 3",
+            },
+        ],
+    },
+    Command {
+        name: "gen",
+        about: "Generate a random input for a day with a registered generator into data/generated",
+        positional: Positional::Day,
+        flags: &[
+            Flag {
+                name: "--size",
+                value: Some("<n>"),
+                choices: &[],
+                path: false,
+                help: "Size of the input, e.g. the number of lines, defaults to 1000",
+            },
+            Flag {
+                name: "--seed",
+                value: Some("<seed>"),
+                choices: &[],
+                path: false,
+                help: "Seed of the generator, the same seed gives the same input, defaults to 1",
+            },
+        ],
+    },
+    Command {
+        name: "asm",
+        about: "Show the disassembly of a day's parts and the functions they call in the release build",
+        positional: Positional::DayAndPart,
+        flags: &[
+            switch(
+                "--diff",
+                "Show the changes since the previous `asm` of the part",
+            ),
+            switch("--llvm-ir", "Show the LLVM IR instead of the disassembly"),
+        ],
+    },
+    Command {
+        name: "profile",
+        about: "Count cycles, instructions, cache and branch misses per run of a day's parts with perf",
+        positional: Positional::Day,
+        flags: &[
+            Flag {
+                name: "--part",
+                value: Some("<part>"),
+                choices: &["1", "2"],
+                path: false,
+                help: "Only profile part 1 or 2",
+            },
+            Flag {
+                name: "--iterations",
+                value: Some("<n>"),
+                choices: &[],
+                path: false,
+                help: "Runs of the part to measure, defaults to about a second's worth",
+            },
+            switch(
+                "--flamegraph",
+                "Also sample the part with perf record and write a flamegraph SVG",
+            ),
+        ],
+    },
+    Command {
+        name: "watch",
+        about: "Re-run the tests and solution for a day whenever its files change",
+        positional: Positional::Day,
+        flags: &[switch("--release", "Build with optimisations")],
+    },
+    Command {
+        name: "status",
+        about: "Show the progress of every day, or the selected days, as a table",
+        positional: Positional::OptionalDays,
+        flags: &[switch(
+            "--check",
+            "Run the tests and solutions to check the parts and answers",
+        )],
+    },
+    Command {
+        name: "inputs",
+        about: "Encrypt the stored inputs and puzzles, or pack them into one encrypted file to share",
+        positional: Positional::InputsAction,
+        flags: &[Flag {
+            name: "--file",
+            value: Some("<path>"),
+            choices: &[],
+            path: true,
+            help: "File to pack into or unpack from, defaults to data/inputs.pack",
+        }],
+    },
+    #[cfg(feature = "today")]
+    Command {
+        name: "today",
+        about: "Scaffold, download and read the current day of advent",
+        positional: Positional::None,
+        flags: &[],
+    },
+    Command {
+        name: "completions",
+        about: "Print a shell completion script",
+        positional: Positional::Shell,
+        flags: &[],
+    },
+];
+
+const DAYS_HELP: &str = "A day between 1 and 25, a range like 1-5, a list like 1,4,9, or one of\nall, unsolved (scaffolded but not fully benchmarked) and last";
+
+const INPUTS_ACTION_HELP: &str = "pack, unpack, or encrypt / decrypt the stored files in place";
+
+/// Looks up a subcommand by name.
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+impl Command {
+    /// Single line usage, e.g. `solve <day> [--release] [--dhat] [--submit <part>]`.
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        match self.positional {
+            Positional::None => {}
+            Positional::Day => usage.push_str(" <day>"),
+            Positional::DayAndPart => usage.push_str(" <day> [part]"),
+            Positional::Days => usage.push_str(" <days>"),
+            Positional::OptionalDays => usage.push_str(" [days]"),
+            Positional::Shell => usage.push_str(" <shell>"),
+            Positional::InputsAction => usage.push_str(" <action>"),
+        }
+        for flag in self.flags {
+            match flag.value {
+                Some(value) => {
+                    let _ = write!(usage, " [{} {value}]", flag.name);
+                }
+                None => {
+                    let _ = write!(usage, " [{}]", flag.name);
+                }
+            }
+        }
+        usage
+    }
+
+    /// Detailed help for this command.
+    pub fn help(&self) -> String {
+        let mut help = format!(
+            "{}\n\n{ANSI_BOLD}Usage:{ANSI_RESET} {BIN_NAME} {}\n",
+            self.about,
+            self.usage()
+        );
+
+        let positional = match self.positional {
+            Positional::None => None,
+            Positional::Day => Some(("<day>", "A single day, e.g. 3 or last")),
+            Positional::DayAndPart => Some((
+                "<day> [part]",
+                "A single day, e.g. 3 or last, optionally followed by part 1 or 2",
+            )),
+            Positional::Days => Some(("<days>", DAYS_HELP)),
+            Positional::OptionalDays => Some(("[days]", DAYS_HELP)),
+            Positional::Shell => Some(("<shell>", "One of: bash, zsh, fish")),
+            Positional::InputsAction => Some(("<action>", INPUTS_ACTION_HELP)),
+        };
+        let flags: Vec<(String, &str)> = self
+            .flags
+            .iter()
+            .map(|flag| match flag.value {
+                Some(value) => (format!("{} {value}", flag.name), flag.help),
+                None => (flag.name.to_string(), flag.help),
+            })
+            .chain([("-h, --help".to_string(), "Print this help")])
+            .collect();
+        // the descriptions line up two spaces after the longest name.
+        let width = flags
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .chain(positional.map(|(name, _)| name.chars().count()))
+            .max()
+            .unwrap_or(0)
+            + 2;
+
+        // continuation lines of a description line up with its first line.
+        let indent = format!("\n{}", " ".repeat(width + 2));
+
+        if let Some((name, text)) = positional {
+            let text = text.replace('\n', &indent);
+            let _ = write!(
+                help,
+                "\n{ANSI_BOLD}Arguments:{ANSI_RESET}\n  {name:<width$}{text}\n"
+            );
+        }
+
+        help.push_str(&format!("\n{ANSI_BOLD}Options:{ANSI_RESET}\n"));
+        for (name, text) in flags {
+            let _ = writeln!(help, "  {name:<width$}{text}");
+        }
+        help
+    }
+}
+
+/// Overview of all commands.
+pub fn help() -> String {
+    let mut help = format!(
+        "🎄 Advent of Code helper\n\n{ANSI_BOLD}Usage:{ANSI_RESET} {BIN_NAME} <command> [args]\n\n{ANSI_BOLD}Commands:{ANSI_RESET}\n"
+    );
+    for command in COMMANDS {
+        let _ = writeln!(help, "  {:<13}{}", command.name, command.about);
+    }
+    let _ = writeln!(help, "  {:<13}Print help for a command", "help");
+    help.push_str(&format!(
+        "\nRun `{BIN_NAME} help <command>` or `{BIN_NAME} <command> --help` for details.\n"
+    ));
+    help
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A shell that completion scripts can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = ArgsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(ArgsError::InvalidValue {
+                name: "<shell>",
+                value: s.to_string(),
+                expected: "one of bash, zsh, fish",
+            }),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What the `inputs` command does with the stored inputs and puzzles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputsAction {
+    /// Bundle all of them into a single encrypted file.
+    Pack,
+    /// Restore them from a file created by [`InputsAction::Pack`].
+    Unpack,
+    Encrypt,
+    Decrypt,
+}
+
+impl FromStr for InputsAction {
+    type Err = ArgsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pack" => Ok(InputsAction::Pack),
+            "unpack" => Ok(InputsAction::Unpack),
+            "encrypt" => Ok(InputsAction::Encrypt),
+            "decrypt" => Ok(InputsAction::Decrypt),
+            _ => Err(ArgsError::InvalidValue {
+                name: "<action>",
+                value: s.to_string(),
+                expected: "one of pack, unpack, encrypt, decrypt",
+            }),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where `solve --visualize` shows the frames recorded with `advent_of_code::viz`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visualize {
+    /// Play them back in the terminal.
+    Terminal,
+    /// Write them to `data/frames/` as PPM images.
+    Frames,
+}
+
+impl Visualize {
+    /// The value of the `--visualize` argument of the solution binaries.
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            Visualize::Terminal => "terminal",
+            Visualize::Frames => "frames",
+        }
+    }
+}
+
+impl FromStr for Visualize {
+    type Err = ArgsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Visualize::Terminal),
+            "frames" => Ok(Visualize::Frames),
+            _ => Err(ArgsError::InvalidValue {
+                name: "--visualize",
+                value: s.to_string(),
+                expected: "one of terminal, frames",
+            }),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What `scaffold` does with a file that already has contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Existing {
+    #[default]
+    Skip,
+    Overwrite,
+    /// Move the existing file to a `.bak` path, then write the new one.
+    Backup,
+}
+
+impl FromStr for Existing {
+    type Err = ArgsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Existing::Skip),
+            "overwrite" => Ok(Existing::Overwrite),
+            "backup" => Ok(Existing::Backup),
+            _ => Err(ArgsError::InvalidValue {
+                name: "--existing",
+                value: s.to_string(),
+                expected: "one of skip, overwrite, backup",
+            }),
+        }
+    }
+}
+
+/// The [`Existing`] behaviour for the module and the examples `scaffold` creates. The input is
+/// never replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExistingPolicy {
+    pub module: Existing,
+    pub example: Existing,
+}
+
+impl ExistingPolicy {
+    /// Applies a spec like `backup` or `module=overwrite,input=skip` on top of this policy.
+    /// A bare value applies to every file.
+    pub fn apply(&mut self, spec: &str) -> Result<(), ArgsError> {
+        for item in spec.split(',').map(str::trim) {
+            match item.split_once('=') {
+                None => {
+                    let existing = item.parse()?;
+                    self.module = existing;
+                    self.example = existing;
+                }
+                Some(("module", value)) => self.module = value.parse()?,
+                Some(("example", value)) => self.example = value.parse()?,
+                Some(_) => {
+                    return Err(ArgsError::InvalidValue {
+                        name: "--existing",
+                        value: item.to_string(),
+                        expected: "one of module, example before `=`",
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing command-line arguments.
+#[derive(Debug)]
+pub enum ArgsError {
+    NoCommand,
+    UnknownCommand(String),
+    MissingArgument(&'static str),
+    InvalidDays {
+        value: String,
+        source: DaySelectorError,
+    },
+    InvalidValue {
+        name: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+    /// Two flags that cannot be combined.
+    Conflict(&'static str, &'static str),
+    UnknownFlags(Vec<String>),
+    UnexpectedArguments(Vec<String>),
+    Parse(pico_args::Error),
+}
+
+impl Error for ArgsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ArgsError::InvalidDays { source, .. } => Some(source),
+            ArgsError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::NoCommand => write!(f, "no command specified."),
+            ArgsError::UnknownCommand(name) => write!(f, "unknown command `{name}`."),
+            ArgsError::MissingArgument(name) => write!(f, "missing argument {name}."),
+            ArgsError::InvalidDays { value, source } => {
+                write!(f, "invalid days `{value}`: {source}.")
+            }
+            ArgsError::InvalidValue {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value `{value}` for {name}: expecting {expected}."
+            ),
+            ArgsError::Conflict(a, b) => write!(f, "{a} cannot be used together with {b}."),
+            ArgsError::UnknownFlags(flags) => write!(f, "unknown flag(s): {}.", flags.join(", ")),
+            ArgsError::UnexpectedArguments(args) => {
+                write!(f, "unexpected argument(s): {}.", args.join(", "))
+            }
+            ArgsError::Parse(e) => write!(f, "{e}."),
+        }
+    }
+}
+
+impl From<pico_args::Error> for ArgsError {
+    fn from(e: pico_args::Error) -> Self {
+        ArgsError::Parse(e)
+    }
+}
+
+/// Parses a day selector, keeping the original value around for error messages.
+pub fn parse_days(value: &str) -> Result<DaySelector, ArgsError> {
+    value.parse().map_err(|source| ArgsError::InvalidDays {
+        value: value.to_string(),
+        source,
+    })
+}
+
+/// Turns arguments left over after parsing into an error, if there are any.
+pub fn check_remaining(remaining: Vec<OsString>) -> Result<(), ArgsError> {
+    let (flags, free): (Vec<String>, Vec<String>) = remaining
+        .into_iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .partition(|arg| arg.starts_with('-'));
+
+    if !flags.is_empty() {
+        Err(ArgsError::UnknownFlags(flags))
+    } else if !free.is_empty() {
+        Err(ArgsError::UnexpectedArguments(free))
+    } else {
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        ArgsError, COMMANDS, Existing, ExistingPolicy, check_remaining, find_command, parse_days,
+    };
+
+    #[test]
+    fn commands_are_well_formed() {
+        for command in COMMANDS {
+            assert_eq!(find_command(command.name).unwrap().name, command.name);
+            assert!(command.flags.iter().all(|f| f.name.starts_with("--")));
+            assert!(
+                command
+                    .flags
+                    .iter()
+                    .all(|f| f.value.is_some() || f.choices.is_empty())
+            );
+        }
+        assert!(find_command("help").is_none());
+    }
+
+    #[test]
+    fn renders_usage() {
+        let solve = find_command("solve").unwrap();
+        assert_eq!(
+            solve.usage(),
+            "solve <days> [--release] [--dhat] [--submit <part>] [--input <name|path>] [--each-input] [--visualize <mode>] [--fps <n>] [--debug]"
+        );
+        assert!(solve.help().contains("--submit <part>"));
+
+        // the longest flag still has two spaces before its description.
+        let time = find_command("time").unwrap().help();
+        assert!(time.contains("  --input <name|path>  Run against"));
+    }
+
+    #[test]
+    fn parses_days() {
+        assert_eq!(parse_days("7").unwrap(), "07".parse().unwrap());
+        assert!(parse_days("1-5,last").is_ok());
+        assert!(matches!(
+            parse_days("0"),
+            Err(ArgsError::InvalidDays { .. })
+        ));
+        assert!(matches!(
+            parse_days("x"),
+            Err(ArgsError::InvalidDays { .. })
+        ));
+        assert!(matches!(
+            parse_days("4-2"),
+            Err(ArgsError::InvalidDays { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_existing_policies() {
+        let mut policy = ExistingPolicy::default();
+        assert_eq!(policy.module, Existing::Skip);
+
+        policy.apply("backup,module=overwrite").unwrap();
+        assert_eq!(
+            policy,
+            ExistingPolicy {
+                module: Existing::Overwrite,
+                example: Existing::Backup,
+            }
+        );
+
+        policy.apply("example=skip").unwrap();
+        assert_eq!(policy.module, Existing::Overwrite);
+        assert_eq!(policy.example, Existing::Skip);
+
+        assert!(policy.apply("keep").is_err());
+        assert!(policy.apply("readme=skip").is_err());
+        // the input is never replaced, see `scaffold`.
+        assert!(policy.apply("input=overwrite").is_err());
+    }
+
+    #[test]
+    fn rejects_leftover_arguments() {
+        assert!(check_remaining(vec![]).is_ok());
+        assert!(matches!(
+            check_remaining(vec!["--bogus".into(), "extra".into()]),
+            Err(ArgsError::UnknownFlags(flags)) if flags == vec!["--bogus".to_string()]
+        ));
+        assert!(matches!(
+            check_remaining(vec!["extra".into()]),
+            Err(ArgsError::UnexpectedArguments(_))
+        ));
+    }
+}
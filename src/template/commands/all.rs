@@ -1,5 +1,41 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process::{Command, Stdio};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{Day, run_multi::run_multi};
+
+pub fn handle(days: &[Day], is_release: bool, in_process: bool, input: Option<&str>) {
+    if in_process {
+        run_in_process(days, is_release, input);
+    } else {
+        run_multi(&days.iter().copied().collect(), is_release, false, input);
+    }
+}
+
+/// Runs the selected days through the single `all_days` binary.
+fn run_in_process(days: &[Day], is_release: bool, input: Option<&str>) {
+    let mut args = vec![
+        "run",
+        "--quiet",
+        "--features",
+        "all-days",
+        "--bin",
+        "all_days",
+    ];
+    if is_release {
+        args.push("--release");
+    }
+    args.push("--");
+    let days: Vec<String> = days.iter().map(Day::to_string).collect();
+    args.extend(days.iter().map(String::as_str));
+    if let Some(input) = input {
+        args.extend(["--input", input]);
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
 }
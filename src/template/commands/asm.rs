@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    time::SystemTime,
+};
+
+use crate::template::{Day, PART_FNS};
+
+/// The lines of each function in a listing, by demangled name.
+type Functions = BTreeMap<String, Vec<String>>;
+
+/// The directory cargo builds into.
+pub fn target_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from)
+}
+
+/// Name of the crate of a day's binary, e.g. `_03`.
+fn crate_name(day: Day) -> String {
+    format!("_{day}")
+}
+
+fn run(command: &mut Command) -> Result<String, String> {
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to run {command:?}: {e}"))?;
+    if !output.status.success() {
+        return Err(format!("{command:?} failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Builds the day with the release profile, as it is benchmarked, and returns its binary.
+pub fn build_release(day: Day) -> Result<PathBuf, String> {
+    run(Command::new("cargo").args(["build", "--quiet", "--release", "--bin", &day.to_string()]))?;
+    Ok(target_dir().join("release").join(day.to_string()))
+}
+
+fn disassemble(day: Day) -> Result<String, String> {
+    let binary = build_release(day)?;
+    run(Command::new("objdump")
+        .args([
+            "-d",
+            "-C",
+            "-M",
+            "intel",
+            "--no-show-raw-insn",
+            "--no-addresses",
+        ])
+        .arg(binary))
+}
+
+/// The LLVM IR files of a day in the build directory, with when they were written.
+fn day_ir_files(day: Day) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    fs::read_dir(target_dir().join("release").join("deps"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|e| e == "ll")
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(&format!("{day}-")))
+        })
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Builds the day with the release profile and returns its optimised LLVM IR, one module per
+/// codegen unit as in the binary.
+fn emit_llvm_ir(day: Day) -> Result<String, String> {
+    let saved = target_dir().join("asm").join(format!("{day}-ir"));
+    let before = day_ir_files(day);
+    // rustc compiles into a single codegen unit when it only has to write IR, which inlines the
+    // parts into `main`. Also link and split into 16 codegen units, rustc's default for builds
+    // without incremental compilation. This overrides `codegen-units` in `[profile.release]`, so
+    // with a different setting there the IR can differ from the code in the binary.
+    run(Command::new("cargo")
+        .args(["rustc", "--quiet", "--release", "--bin", &day.to_string()])
+        .args(["--", "--emit=llvm-ir,link", "-C", "codegen-units=16"]))?;
+
+    // earlier builds may have left modules of codegen units that no longer exist, keep only
+    // the ones this build wrote.
+    let written: Vec<PathBuf> = day_ir_files(day)
+        .into_iter()
+        .filter(|(path, modified)| before.get(path) != Some(modified))
+        .map(|(path, _)| path)
+        .collect();
+    if !written.is_empty() {
+        let _ = fs::remove_dir_all(&saved);
+        fs::create_dir_all(&saved).map_err(|e| e.to_string())?;
+        for path in written {
+            fs::copy(&path, saved.join(path.file_name().unwrap())).map_err(|e| e.to_string())?;
+        }
+    }
+
+    // cargo skips rustc when the day is unchanged, the IR of the last build is still current then.
+    let mut modules: Vec<PathBuf> = fs::read_dir(&saved)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    if modules.is_empty() {
+        return Err(format!(
+            "rustc did not write any LLVM IR, touch src/bin/{day}.rs to rebuild the day"
+        ));
+    }
+    modules.sort();
+    let mut ir = String::new();
+    for path in modules {
+        ir += &fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read \"{}\": {e}", path.display()))?;
+    }
+    Ok(ir)
+}
+
+/// Splits the output of `objdump -d -C --no-addresses` into functions.
+fn parse_objdump(output: &str) -> Functions {
+    let mut functions = Functions::new();
+    let mut current: Option<(String, Vec<String>)> = None;
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix('<').and_then(|l| l.strip_suffix(">:")) {
+            current = Some((name.to_string(), vec![]));
+        } else if line.trim().is_empty() {
+            if let Some((name, lines)) = current.take() {
+                functions.insert(name, lines);
+            }
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line.trim_start().replace('\t', " "));
+        }
+    }
+    if let Some((name, lines)) = current {
+        functions.insert(name, lines);
+    }
+    functions
+}
+
+/// Turns a legacy Rust symbol like `_ZN3_038part_one17h0123456789abcdefE` into `_03::part_one`.
+/// Other symbols are returned unchanged.
+fn demangle(symbol: &str) -> String {
+    let Some(mut rest) = symbol.strip_prefix("_ZN") else {
+        return symbol.to_string();
+    };
+    let mut path = vec![];
+    while let Some(digits) = rest.find(|c: char| !c.is_ascii_digit()).filter(|&i| i > 0) {
+        let Ok(len) = rest[..digits].parse::<usize>() else {
+            break;
+        };
+        let Some(segment) = rest.get(digits..digits + len) else {
+            break;
+        };
+        path.push(segment);
+        rest = &rest[digits + len..];
+    }
+    if rest != "E" || path.is_empty() {
+        return symbol.to_string();
+    }
+    // the last segment is the hash.
+    if path
+        .last()
+        .is_some_and(|s| s.len() == 17 && s.starts_with('h'))
+    {
+        path.pop();
+    }
+    path.join("::")
+}
+
+/// The name of the function in `@name(` or `@"name"(`.
+fn ir_function_name(text: &str) -> Option<String> {
+    let start = text.find('@')? + 1;
+    let name = text[start..].split('(').next()?;
+    Some(demangle(name.trim_matches('"')))
+}
+
+/// Splits LLVM IR into the functions it defines.
+fn parse_llvm_ir(ir: &str) -> Functions {
+    let mut functions = Functions::new();
+    let mut current: Option<(String, Vec<String>)> = None;
+    for line in ir.lines() {
+        if line.starts_with("define ") {
+            current = ir_function_name(line).map(|name| (name, vec![line.to_string()]));
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line.to_string());
+            if line == "}" {
+                let (name, lines) = current.take().unwrap();
+                functions.insert(name, lines);
+            }
+        }
+    }
+    functions
+}
+
+/// Functions called from `lines`, in assembly (`call <name>`) or LLVM IR (`call ... @name(...)`).
+fn callees(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let mnemonic = line.split_whitespace().next()?;
+            if mnemonic == "call" || mnemonic == "jmp" {
+                let target = line[line.find('<')? + 1..line.rfind('>')?].to_string();
+                // jumps within a function are shown as `<name+0x1f>`.
+                (!target.contains("+0x")).then_some(target)
+            } else if line.contains("call ") || line.contains("invoke ") {
+                ir_function_name(&line[line.find("call ").or(line.find("invoke "))?..])
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// `root` and the functions it calls, transitively, that are in `functions` and accepted by
+/// `include`. Calls that were inlined are already part of the function that made them.
+fn reachable<'a>(
+    functions: &'a Functions,
+    root: &str,
+    include: impl Fn(&str) -> bool,
+) -> Vec<(&'a str, &'a [String])> {
+    let mut seen = BTreeSet::from([root.to_string()]);
+    let mut queue = VecDeque::from([root.to_string()]);
+    let mut found = vec![];
+    while let Some(name) = queue.pop_front() {
+        let Some((name, lines)) = functions.get_key_value(&name) else {
+            continue;
+        };
+        found.push((name.as_str(), lines.as_slice()));
+        for callee in callees(lines) {
+            if functions.contains_key(&callee) && include(&callee) && seen.insert(callee.clone()) {
+                queue.push_back(callee);
+            }
+        }
+    }
+    found
+}
+
+/// The listing of a part and its non-inlined callees.
+fn listing(day: Day, part: u8, functions: &Functions, llvm_ir: bool) -> Result<String, String> {
+    let crate_name = crate_name(day);
+    let root = format!("{crate_name}::{}", PART_FNS[usize::from(part - 1)]);
+    if !functions.contains_key(&root) {
+        return Err(format!(
+            "`{root}` is not in the build, it was probably inlined into `main`. Mark it with `#[inline(never)]` to inspect it"
+        ));
+    }
+
+    // IR only contains the crate's functions and the generic functions it instantiates, the
+    // binary also contains the standard library.
+    let include = |name: &str| {
+        llvm_ir || name.contains(&format!("{crate_name}::")) || name.contains("advent_of_code::")
+    };
+    let mut text = String::new();
+    for (name, lines) in reachable(functions, &root, include) {
+        text.push_str(&format!("{name}:\n"));
+        for line in lines {
+            text.push_str(&format!("    {line}\n"));
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Where the last listing of a part is kept for `--diff`.
+fn saved_path(day: Day, part: u8, llvm_ir: bool) -> PathBuf {
+    let extension = if llvm_ir { "ll" } else { "s" };
+    target_dir()
+        .join("asm")
+        .join(format!("{day}-part{part}.{extension}"))
+}
+
+/// Prints a unified diff between the saved listing and `text`.
+fn print_diff(previous: &Path, text: &str) -> Result<(), String> {
+    let current = previous.with_extension("new");
+    fs::write(&current, text).map_err(|e| e.to_string())?;
+    let output = Command::new("diff")
+        .args(["-u", "--label", "previous", "--label", "current"])
+        .args([previous, &current])
+        .output()
+        .map_err(|e| format!("failed to run diff: {e}"));
+    let _ = fs::remove_file(&current);
+    let output = output?;
+    if output.stdout.is_empty() {
+        println!("No changes since the previous build.");
+    } else {
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(())
+}
+
+fn fail(e: impl std::fmt::Display) -> ! {
+    eprintln!("Error: {e}.");
+    process::exit(1);
+}
+
+pub fn handle(day: Day, part: Option<u8>, diff: bool, llvm_ir: bool) {
+    let functions = if llvm_ir {
+        emit_llvm_ir(day).map(|ir| parse_llvm_ir(&ir))
+    } else {
+        disassemble(day).map(|output| parse_objdump(&output))
+    }
+    .unwrap_or_else(|e| fail(e));
+
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    for (i, &part) in parts.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let text = listing(day, part, &functions, llvm_ir).unwrap_or_else(|e| fail(e));
+        let path = saved_path(day, part, llvm_ir);
+
+        if diff && path.exists() {
+            print_diff(&path, &text).unwrap_or_else(|e| fail(e));
+        } else {
+            if diff {
+                println!("No previous build of part {part} to compare with, showing it instead.\n");
+            }
+            print!("{text}");
+        }
+
+        if let Err(e) =
+            fs::create_dir_all(path.parent().unwrap()).and_then(|()| fs::write(&path, &text))
+        {
+            fail(format!("failed to save \"{}\": {e}", path.display()));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const OBJDUMP: &str = "
+target/release/03:     file format elf64-x86-64
+
+
+Disassembly of section .text:
+
+<_03::part_one>:
+\tpush   rbp
+\tcall   <_03::max_joltage>
+\tcall   <<core::str::iter::Lines as core::iter::traits::iterator::Iterator>::next>
+\tje     <_03::part_one+0x40>
+\tret
+
+<_03::max_joltage>:
+\tmov    eax,0x1
+\tret
+
+<<core::str::iter::Lines as core::iter::traits::iterator::Iterator>::next>:
+\tret
+";
+
+    #[test]
+    fn extracts_parts_and_their_callees() {
+        let functions = parse_objdump(OBJDUMP);
+        assert_eq!(functions.len(), 3);
+        assert_eq!(functions["_03::max_joltage"], ["mov    eax,0x1", "ret"]);
+
+        let text = listing(crate::day!(3), 1, &functions, false).unwrap();
+        assert!(text.starts_with("_03::part_one:\n    push   rbp\n"));
+        assert!(text.contains("_03::max_joltage:\n"));
+        assert!(!text.contains("Lines as core::iter::traits::iterator::Iterator>::next>:"));
+
+        assert!(listing(crate::day!(3), 2, &functions, false).is_err());
+    }
+
+    #[test]
+    fn reads_llvm_ir() {
+        let ir = r#"
+; ModuleID = '_03.b1c2d3-cgu.0'
+define internal fastcc i64 @_ZN3_038part_one17h0123456789abcdefE(ptr %input) unnamed_addr {
+start:
+  %0 = call i64 @_ZN3_0311max_joltage17hfedcba9876543210E(ptr %input)
+  ret i64 %0
+}
+
+define internal i64 @_ZN3_0311max_joltage17hfedcba9876543210E(ptr %input) {
+  ret i64 1
+}
+declare i64 @llvm.ctpop.i64(i64)
+"#;
+        let functions = parse_llvm_ir(ir);
+        assert_eq!(
+            functions.keys().collect::<Vec<_>>(),
+            ["_03::max_joltage", "_03::part_one"]
+        );
+        let text = listing(crate::day!(3), 1, &functions, true).unwrap();
+        assert!(text.contains("_03::max_joltage:\n"));
+    }
+
+    #[test]
+    fn demangles_legacy_symbols() {
+        assert_eq!(
+            demangle("_ZN3_038part_one17h0123456789abcdefE"),
+            "_03::part_one"
+        );
+        assert_eq!(
+            demangle("_ZN14advent_of_code5graph4prim17h0123456789abcdefE"),
+            "advent_of_code::graph::prim"
+        );
+        assert_eq!(demangle("llvm.ctpop.i64"), "llvm.ctpop.i64");
+        assert_eq!(demangle("_ZN3_03"), "_ZN3_03");
+    }
+}
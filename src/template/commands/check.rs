@@ -0,0 +1,246 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::commands::generate;
+use crate::template::run_multi::{child_commands, print_table};
+use crate::template::runner::variant_part;
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, Day, PART_FNS, registry};
+
+/// A part or one of its variants.
+struct Implementation {
+    name: &'static str,
+    part: u8,
+    variant: Option<&'static str>,
+}
+
+/// The non-empty examples of a day, e.g. `data/examples/08.txt` and `data/examples/08-2.txt`.
+fn examples(day: Day) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir("data/examples")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let is_example = name == format!("{day}.txt")
+                || name.starts_with(&format!("{day}-")) && name.ends_with(".txt");
+            is_example && entry.metadata().is_ok_and(|m| m.len() > 0)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// The inputs the implementations are compared on: the examples, the puzzle input and, if the
+/// day has a generator, `seeds` generated inputs.
+fn inputs(day: Day, has_generator: bool, size: usize, seeds: u64) -> Vec<PathBuf> {
+    let mut inputs = examples(day);
+
+    let input = PathBuf::from(format!("data/inputs/{day}.txt"));
+    if fs::metadata(&input).is_ok_and(|m| m.len() > 0) {
+        inputs.push(input);
+    }
+
+    if has_generator {
+        for seed in 1..=seeds {
+            match generate::generate(day, size, seed) {
+                Ok(path) => inputs.push(path),
+                Err(e) => {
+                    eprintln!("Skipping generated inputs: {e}.");
+                    break;
+                }
+            }
+        }
+    }
+    inputs
+}
+
+fn label(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    path.strip_prefix("data/").unwrap_or(&path).to_string()
+}
+
+/// Compares the answers of every implementation on each input and returns the number of inputs
+/// where a variant disagrees with its part.
+fn compare(day: Day, implementations: &[Implementation], inputs: &[PathBuf]) -> usize {
+    let mut header = vec!["Input".to_string()];
+    header.extend(implementations.iter().map(|i| i.name.to_string()));
+    header.push(String::new());
+    let mut rows = vec![header];
+
+    let mut mismatches = 0;
+    for path in inputs {
+        let path_str = path.to_string_lossy();
+        let run = |variant: Option<&str>| match child_commands::capture_solution(
+            day,
+            false,
+            true,
+            Some(&path_str),
+            variant,
+        ) {
+            Ok(output) => child_commands::parse_answers(&output),
+            Err(e) => {
+                let name = variant.unwrap_or("The solution");
+                eprintln!("{name} failed on {}: {e}", label(path));
+                [None, None]
+            }
+        };
+        let base = run(None);
+
+        let answers: Vec<Option<String>> = implementations
+            .iter()
+            .map(|implementation| {
+                let part = usize::from(implementation.part - 1);
+                match implementation.variant {
+                    None => base[part].clone(),
+                    Some(variant) => run(Some(variant))[part].clone(),
+                }
+            })
+            .collect();
+
+        // a part without an answer cannot be compared, so it counts as a mismatch.
+        let agree = implementations
+            .iter()
+            .zip(&answers)
+            .all(|(implementation, answer)| {
+                answer.is_some() && *answer == base[usize::from(implementation.part - 1)]
+            });
+        if !agree {
+            mismatches += 1;
+        }
+
+        let verdict = match (agree, answers.contains(&None)) {
+            (true, _) => "✔",
+            (false, true) => "✖ no answer",
+            (false, false) => "✖ differs",
+        };
+        let mut row = vec![label(path)];
+        row.extend(
+            answers
+                .into_iter()
+                .map(|answer| answer.unwrap_or("✖".into())),
+        );
+        row.push(verdict.into());
+        rows.push(row);
+    }
+
+    print_table(&rows);
+    mismatches
+}
+
+/// Benches every implementation on `path` and prints the times relative to the part.
+fn bench(day: Day, implementations: &[Implementation], path: &Path) {
+    let path_str = path.to_string_lossy();
+    let run = |variant: Option<&str>| {
+        child_commands::capture_solution(day, true, true, Some(&path_str), variant).unwrap_or_else(
+            |e| {
+                let name = variant.unwrap_or("The solution");
+                eprintln!("{name} failed on {}: {e}", label(path));
+                vec![]
+            },
+        )
+    };
+    let base = run(None);
+    let base_nanos = child_commands::parse_part_nanos(&base);
+    let base_times = child_commands::parse_part_times(&base);
+
+    let mut rows = vec![["Implementation", "Time", "Relative"].map(String::from)];
+    for implementation in implementations {
+        let part = usize::from(implementation.part - 1);
+        let (time, nanos) = match implementation.variant {
+            None => (base_times[part].clone(), base_nanos[part]),
+            Some(variant) => {
+                let output = run(Some(variant));
+                (
+                    child_commands::parse_part_times(&output)[part].clone(),
+                    child_commands::parse_part_nanos(&output)[part],
+                )
+            }
+        };
+        let relative = nanos
+            .zip(base_nanos[part])
+            .map_or_else(String::new, |(nanos, base)| format!("{:.2}x", nanos / base));
+        rows.push([
+            implementation.name.to_string(),
+            time.unwrap_or("✖".into()),
+            relative,
+        ]);
+    }
+
+    println!();
+    println!("Timings on {}:", label(path));
+    print_table(&rows);
+}
+
+pub fn handle(day: Day, size: usize, seeds: u64) {
+    let Some(info) = registry::get(day) else {
+        eprintln!("Error: day {day} is not scaffolded.");
+        process::exit(1);
+    };
+    if info.variants.is_empty() {
+        eprintln!(
+            "Error: day {day} has no variants, register them with `solution!({}, variants = [part_two_fast])`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    }
+
+    // each part that has variants, followed by its variants.
+    let mut implementations = vec![];
+    for part in [1, 2] {
+        let variants: Vec<&str> = info
+            .variants
+            .iter()
+            .map(String::as_str)
+            .filter(|&variant| variant_part(variant) == part)
+            .collect();
+        if variants.is_empty() {
+            continue;
+        }
+        implementations.push(Implementation {
+            name: PART_FNS[usize::from(part - 1)],
+            part,
+            variant: None,
+        });
+        implementations.extend(variants.into_iter().map(|variant| Implementation {
+            name: variant,
+            part,
+            variant: Some(variant),
+        }));
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    let inputs = inputs(day, info.generator, size, seeds);
+    if inputs.is_empty() {
+        println!("No examples, inputs or generator to check the variants on.");
+        return;
+    }
+    let mismatches = compare(day, &implementations, &inputs);
+
+    // bench on the puzzle input, or the last generated input if there is none.
+    let puzzle_input = PathBuf::from(format!("data/inputs/{day}.txt"));
+    let bench_input = inputs
+        .iter()
+        .find(|&path| *path == puzzle_input)
+        .or_else(|| {
+            inputs
+                .iter()
+                .rev()
+                .find(|path| path.starts_with(generate::GENERATED_DIR))
+        });
+    if let Some(path) = bench_input {
+        bench(day, &implementations, path);
+    }
+
+    println!();
+    if mismatches > 0 {
+        println!("{ANSI_RED}✖ The variants disagree on {mismatches} input(s).{ANSI_RESET}");
+        process::exit(1);
+    }
+    println!("{ANSI_GREEN}✔ All variants agree.{ANSI_RESET}");
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+
+use crate::template::cli::{BIN_NAME, COMMANDS, Command, Positional, Shell};
+
+const DAYS: &str =
+    "all unsolved last 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25";
+const SHELLS: &str = "bash zsh fish";
+const INPUTS_ACTIONS: &str = "pack unpack encrypt decrypt";
+
+pub fn handle(shell: Shell) {
+    let script = match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    };
+    print!("{script}");
+}
+
+fn positional_words(command: &Command) -> &'static str {
+    match command.positional {
+        Positional::None => "",
+        Positional::Day | Positional::DayAndPart | Positional::Days | Positional::OptionalDays => {
+            DAYS
+        }
+        Positional::Shell => SHELLS,
+        Positional::InputsAction => INPUTS_ACTIONS,
+    }
+}
+
+fn command_names() -> String {
+    let mut names: Vec<&str> = COMMANDS.iter().map(|c| c.name).collect();
+    names.push("help");
+    names.join(" ")
+}
+
+pub fn bash() -> String {
+    let mut cases = String::new();
+    for command in COMMANDS {
+        let words: Vec<&str> = command
+            .flags
+            .iter()
+            .map(|f| f.name)
+            .chain(["--help"])
+            .chain(positional_words(command).split_whitespace())
+            .collect();
+        cases.push_str(&format!(
+            "        {})\n            opts=\"{}\"\n            ;;\n",
+            command.name,
+            words.join(" ")
+        ));
+    }
+
+    // flags like `--input` are shared by several commands, but need a single arm.
+    let mut flag_values: BTreeMap<&str, (bool, Vec<&str>)> = BTreeMap::new();
+    for flag in COMMANDS.iter().flat_map(|c| c.flags) {
+        if flag.value.is_some() {
+            let (path, choices) = flag_values.entry(flag.name).or_default();
+            *path |= flag.path;
+            for choice in flag.choices {
+                if !choices.contains(choice) {
+                    choices.push(choice);
+                }
+            }
+        }
+    }
+    let mut values = String::new();
+    for (name, (path, choices)) in flag_values {
+        // other values, such as numbers, have nothing to offer.
+        let reply = if path {
+            "($(compgen -f -- \"$cur\"))".to_string()
+        } else if choices.is_empty() {
+            "()".to_string()
+        } else {
+            format!("($(compgen -W \"{}\" -- \"$cur\"))", choices.join(" "))
+        };
+        values.push_str(&format!(
+            "        {name})\n            COMPREPLY={reply}\n            return\n            ;;\n"
+        ));
+    }
+
+    format!(
+        r#"_{BIN_NAME}() {{
+    local cur prev opts
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{commands}" -- "$cur"))
+        return
+    fi
+
+    case "$prev" in
+{values}    esac
+
+    case "${{COMP_WORDS[1]}}" in
+{cases}        help)
+            opts="{commands}"
+            ;;
+        *)
+            opts=""
+            ;;
+    esac
+    COMPREPLY=($(compgen -W "$opts" -- "$cur"))
+}}
+
+complete -F _{BIN_NAME} {BIN_NAME}
+"#,
+        commands = command_names(),
+    )
+}
+
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+pub fn zsh() -> String {
+    let descriptions: Vec<String> = COMMANDS
+        .iter()
+        .map(|c| format!("        '{}:{}'", c.name, zsh_escape(c.about)))
+        .chain(["        'help:Print help for a command'".to_string()])
+        .collect();
+
+    let mut cases = String::new();
+    for command in COMMANDS {
+        let mut specs: Vec<String> = command
+            .flags
+            .iter()
+            .map(|flag| match flag.value {
+                Some(value) => format!(
+                    "'{}[{}]:{}:{}'",
+                    flag.name,
+                    zsh_escape(flag.help),
+                    value.trim_matches(|c| c == '<' || c == '>'),
+                    if flag.path {
+                        "_files".to_string()
+                    } else {
+                        format!("({})", flag.choices.join(" "))
+                    }
+                ),
+                None => format!("'{}[{}]'", flag.name, zsh_escape(flag.help)),
+            })
+            .collect();
+        specs.push("'(-h --help)'{-h,--help}'[Print help]'".to_string());
+        match command.positional {
+            Positional::None => {}
+            Positional::Day => specs.push(format!("'1:day:({DAYS})'")),
+            Positional::DayAndPart => {
+                specs.push(format!("'1:day:({DAYS})'"));
+                specs.push("'::part:(1 2)'".to_string());
+            }
+            Positional::Days => specs.push(format!("'1:days:({DAYS})'")),
+            Positional::OptionalDays => specs.push(format!("'::days:({DAYS})'")),
+            Positional::Shell => specs.push(format!("'1:shell:({SHELLS})'")),
+            Positional::InputsAction => specs.push(format!("'1:action:({INPUTS_ACTIONS})'")),
+        }
+        cases.push_str(&format!(
+            "        {})\n            _arguments \\\n                {}\n            ;;\n",
+            command.name,
+            specs.join(" \\\n                ")
+        ));
+    }
+
+    format!(
+        r#"#compdef {BIN_NAME}
+
+_{BIN_NAME}() {{
+    local -a commands
+    commands=(
+{descriptions}
+    )
+
+    if (( CURRENT == 2 )); then
+        _describe 'command' commands
+        return
+    fi
+
+    shift words
+    (( CURRENT-- ))
+
+    case $words[1] in
+{cases}        help)
+            _describe 'command' commands
+            ;;
+    esac
+}}
+
+_{BIN_NAME} "$@"
+"#,
+        descriptions = descriptions.join("\n"),
+    )
+}
+
+fn fish_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub fn fish() -> String {
+    let mut lines = vec![format!("complete -c {BIN_NAME} -f")];
+
+    for command in COMMANDS {
+        lines.push(format!(
+            "complete -c {BIN_NAME} -n \"__fish_use_subcommand\" -a {} -d \"{}\"",
+            command.name,
+            fish_escape(command.about)
+        ));
+    }
+    lines.push(format!(
+        "complete -c {BIN_NAME} -n \"__fish_use_subcommand\" -a help -d \"Print help for a command\""
+    ));
+
+    for command in COMMANDS {
+        let condition = format!("-n \"__fish_seen_subcommand_from {}\"", command.name);
+        for flag in command.flags {
+            let long = flag.name.trim_start_matches("--");
+            let help = fish_escape(flag.help);
+            match flag.value {
+                Some(_) if flag.path => lines.push(format!(
+                    "complete -c {BIN_NAME} {condition} -l {long} -r -F -d \"{help}\""
+                )),
+                Some(_) => lines.push(format!(
+                    "complete -c {BIN_NAME} {condition} -l {long} -x -a \"{}\" -d \"{help}\"",
+                    flag.choices.join(" ")
+                )),
+                None => lines.push(format!(
+                    "complete -c {BIN_NAME} {condition} -l {long} -d \"{help}\""
+                )),
+            }
+        }
+        lines.push(format!(
+            "complete -c {BIN_NAME} {condition} -s h -l help -d \"Print help\""
+        ));
+        let words = positional_words(command);
+        if !words.is_empty() {
+            lines.push(format!("complete -c {BIN_NAME} {condition} -a \"{words}\""));
+        }
+    }
+
+    lines.push(format!(
+        "complete -c {BIN_NAME} -n \"__fish_seen_subcommand_from help\" -a \"{}\"",
+        command_names()
+    ));
+
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bash, fish, zsh};
+
+    #[test]
+    fn completes_each_flag_value_once() {
+        let script = bash();
+        assert_eq!(script.matches("        --input)\n").count(), 1);
+        assert!(
+            script.contains("        --input)\n            COMPREPLY=($(compgen -f -- \"$cur\"))")
+        );
+        assert!(script.contains(
+            "        --part)\n            COMPREPLY=($(compgen -W \"1 2\" -- \"$cur\"))"
+        ));
+    }
+
+    #[test]
+    fn completes_files_only_for_paths() {
+        let script = bash();
+        assert_eq!(script.matches("compgen -f").count(), 2);
+        assert!(script.contains("        --size)\n            COMPREPLY=()\n"));
+
+        let script = zsh();
+        assert!(script.contains(":name|path:_files'"));
+        assert!(script.contains(":n:()'"));
+
+        let script = fish();
+        assert!(script.contains("-l file -r -F"));
+        assert!(script.contains("-l seed -x -a \"\""));
+    }
+}
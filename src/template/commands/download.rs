@@ -1,7 +1,17 @@
-use crate::template::{Day, aoc_cli};
-use std::process;
+use crate::template::{Day, aoc_cli, puzzle};
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    process,
+};
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, example: Option<usize>) {
+    fetch(day);
+    write_examples(day, example);
+}
+
+/// Downloads the input and puzzle description.
+pub fn fetch(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
@@ -14,3 +24,116 @@ pub fn handle(day: Day) {
         process::exit(1);
     };
 }
+
+fn is_empty_file(path: &str) -> bool {
+    fs::metadata(path).map_or(true, |m| m.len() == 0)
+}
+
+/// Asks which code block is the example, defaulting to the likely one.
+fn prompt_example(blocks: &[String], likely: usize) -> usize {
+    println!("The puzzle contains several code blocks:");
+    for (i, block) in blocks.iter().enumerate() {
+        let first_line: String = block
+            .lines()
+            .next()
+            .unwrap_or("")
+            .chars()
+            .take(40)
+            .collect();
+        println!(
+            "  [{}] {first_line} ({} lines)",
+            i + 1,
+            block.lines().count()
+        );
+    }
+    print!("Which one is the example? [{}] ", likely + 1);
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return likely;
+    }
+    match answer.trim().parse::<usize>() {
+        Ok(n) if (1..=blocks.len()).contains(&n) => n - 1,
+        _ => likely,
+    }
+}
+
+/// The example inputs of the downloaded puzzle and the answers it gives for them.
+#[derive(Default)]
+pub struct Examples {
+    /// `(path, contents)` pairs, starting with `data/examples/NN.txt`.
+    pub files: Vec<(String, String)>,
+    pub part_one_answer: Option<String>,
+    /// Only known once part two is unlocked.
+    pub part_two_answer: Option<String>,
+}
+
+/// The examples of the downloaded puzzle. `choice` selects a code block (starting at 1);
+/// without it, the user is asked when there are several candidates, unless the caller is not
+/// `interactive`, e.g. a dry run, in which case the likely example is taken.
+pub fn extract_examples(day: Day, choice: Option<usize>, interactive: bool) -> Examples {
+    let Some(markdown) = puzzle::read_puzzle(day) else {
+        return Examples::default();
+    };
+    let (part_one, part_two) = puzzle::split_parts(&markdown);
+
+    let blocks = puzzle::code_blocks(part_one);
+    let Some(likely) = puzzle::likely_example(&blocks) else {
+        return Examples::default();
+    };
+    let chosen = match choice {
+        Some(n) if n > blocks.len() => {
+            eprintln!(
+                "Puzzle only contains {} code block(s), cannot select example {n}.",
+                blocks.len()
+            );
+            process::exit(1);
+        }
+        Some(n) => n - 1,
+        None if interactive && blocks.len() > 1 && io::stdin().is_terminal() => {
+            prompt_example(&blocks, likely)
+        }
+        None => likely,
+    };
+    let mut examples = Examples {
+        files: vec![(format!("data/examples/{day}.txt"), blocks[chosen].clone())],
+        // the answer for the likely example often follows blocks that illustrate the steps, so
+        // it is the last one of the part rather than the one right after the block.
+        part_one_answer: if chosen == likely {
+            puzzle::example_answer(part_one)
+        } else {
+            puzzle::block_answer(part_one, chosen)
+        },
+        part_two_answer: part_two.and_then(puzzle::example_answer),
+    };
+
+    // part two sometimes comes with an example of its own.
+    let part_two_example = part_two.and_then(|text| {
+        let blocks = puzzle::code_blocks(text);
+        let i = puzzle::likely_example(&blocks)?;
+        Some(blocks[i].clone())
+    });
+    if let Some(example) = part_two_example.filter(|e| *e != blocks[chosen]) {
+        examples
+            .files
+            .push((format!("data/examples/{day}-2.txt"), example));
+    }
+
+    examples
+}
+
+/// Writes the example input(s) from the downloaded puzzle into `data/examples`, leaving files
+/// that already have contents untouched.
+pub fn write_examples(day: Day, choice: Option<usize>) {
+    for (path, example) in extract_examples(day, choice, true).files {
+        if !is_empty_file(&path) {
+            println!("Kept existing example file \"{path}\"");
+            continue;
+        }
+        match fs::write(&path, example) {
+            Ok(()) => println!("🎄 Wrote example to \"{path}\"."),
+            Err(e) => eprintln!("Failed to write example file: {e}"),
+        }
+    }
+}
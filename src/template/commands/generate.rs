@@ -0,0 +1,47 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{Day, registry};
+
+pub const GENERATED_DIR: &str = "data/generated";
+
+/// Where the input generated for `day` with `size` and `seed` is stored.
+pub fn generated_path(day: Day, size: usize, seed: u64) -> PathBuf {
+    PathBuf::from(GENERATED_DIR).join(format!("{day}-size{size}-seed{seed}.txt"))
+}
+
+/// Runs the generator registered for `day` and writes its output to [`generated_path`].
+pub fn generate(day: Day, size: usize, seed: u64) -> Result<PathBuf, String> {
+    if !registry::get(day).is_some_and(|info| info.generator) {
+        return Err(format!(
+            "day {day} has no generator, register one with `solution!({}, generator = generate)`",
+            day.into_inner()
+        ));
+    }
+
+    let output = process::Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day.to_string()])
+        .args(["--", "--generate", "--size", &size.to_string()])
+        .args(["--seed", &seed.to_string()])
+        .stderr(process::Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to run the generator: {e}"))?;
+    if !output.status.success() {
+        return Err("the generator failed".into());
+    }
+
+    let path = generated_path(day, size, seed);
+    fs::create_dir_all(GENERATED_DIR)
+        .and_then(|()| fs::write(&path, output.stdout))
+        .map_err(|e| format!("failed to write \"{}\": {e}", path.display()))?;
+    Ok(path)
+}
+
+pub fn handle(day: Day, size: usize, seed: u64) {
+    match generate(day, size, seed) {
+        Ok(path) => println!("Generated \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Error: {e}.");
+            process::exit(1);
+        }
+    }
+}
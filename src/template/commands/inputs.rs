@@ -0,0 +1,214 @@
+use std::{fs, path::Path, process};
+
+use crate::template::cli::InputsAction;
+use crate::template::encryption::{self, KEY_VAR, Key};
+
+pub const DEFAULT_PACK_PATH: &str = "data/inputs.pack";
+
+/// Directories holding files that must not be published, with the extension of those files.
+const STORED: [(&str, &str); 2] = [("data/inputs", ".txt"), ("data/puzzles", ".md")];
+
+/// Non-empty files in `dir` with the given extension, including those in day folders like
+/// `data/inputs/01/alice.txt`.
+fn files_in(dir: &str, extension: &str) -> Vec<String> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() && is_day_folder(&name) {
+            paths.extend(files_in(&format!("{dir}/{name}"), extension));
+        } else if metadata.is_file() && metadata.len() > 0 && name.ends_with(extension) {
+            paths.push(format!("{dir}/{name}"));
+        }
+    }
+    paths
+}
+
+fn is_day_folder(name: &str) -> bool {
+    name.len() == 2 && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Paths of all stored inputs and puzzles, e.g. `data/inputs/01.txt`.
+fn stored_files() -> Vec<String> {
+    let mut paths: Vec<String> = STORED
+        .iter()
+        .flat_map(|(dir, extension)| files_in(dir, extension))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Whether a path from a pack points into one of the [`STORED`] directories.
+fn is_stored_path(path: &str) -> bool {
+    let is_file_name = |name: &str, extension: &str| {
+        name.ends_with(extension) && !name.contains(['/', '\\']) && !name.starts_with('.')
+    };
+    STORED.iter().any(|(dir, extension)| {
+        let Some(name) = path
+            .strip_prefix(dir)
+            .and_then(|rest| rest.strip_prefix('/'))
+        else {
+            return false;
+        };
+        match name.split_once('/') {
+            Some((folder, name)) => is_day_folder(folder) && is_file_name(name, extension),
+            None => is_file_name(name, extension),
+        }
+    })
+}
+
+/// Serialises files as a sequence of length-prefixed paths and contents.
+fn bundle(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut out = vec![];
+    for (path, contents) in files {
+        for bytes in [path.as_bytes(), contents] {
+            out.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            out.extend_from_slice(bytes);
+        }
+    }
+    out
+}
+
+/// The inverse of [`bundle`], `None` if the data is malformed.
+fn unbundle(mut data: &[u8]) -> Option<Vec<(String, Vec<u8>)>> {
+    let take = |data: &mut &[u8]| -> Option<Vec<u8>> {
+        let (len, rest) = data.split_first_chunk::<8>()?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+        let (bytes, rest) = rest.split_at_checked(len)?;
+        *data = rest;
+        Some(bytes.to_vec())
+    };
+
+    let mut files = vec![];
+    while !data.is_empty() {
+        let path = String::from_utf8(take(&mut data)?).ok()?;
+        let contents = take(&mut data)?;
+        files.push((path, contents));
+    }
+    Some(files)
+}
+
+fn require_key() -> Key {
+    Key::from_env().unwrap_or_else(|| {
+        eprintln!("Set {KEY_VAR} to the key shared with your team.");
+        process::exit(1);
+    })
+}
+
+fn fail(path: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("Failed to process \"{path}\": {e}");
+    process::exit(1);
+}
+
+fn pack(file: &str) {
+    let key = require_key();
+    let files: Vec<(String, Vec<u8>)> = stored_files()
+        .into_iter()
+        .map(|path| match encryption::read(&path) {
+            Ok(contents) => (path, contents),
+            Err(e) => fail(&path, e),
+        })
+        .collect();
+
+    let salt = encryption::salt_for(file);
+    if let Err(e) = fs::write(file, encryption::encrypt(&key, &salt, &bundle(&files))) {
+        fail(file, e);
+    }
+    println!("🔒 Packed {} file(s) into \"{file}\".", files.len());
+}
+
+fn unpack(file: &str) {
+    let key = require_key();
+    let data = fs::read(file).unwrap_or_else(|e| fail(file, e));
+    let plain = encryption::decrypt(&key, &data).unwrap_or_else(|e| fail(file, e));
+    let Some(files) = unbundle(&plain) else {
+        fail(file, "not a file created by `inputs pack`");
+    };
+
+    for (path, contents) in &files {
+        if !is_stored_path(path) {
+            fail(file, format!("refusing to write to \"{path}\""));
+        }
+        if let Some(parent) = Path::new(path).parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Err(e) = encryption::write(path, contents) {
+            fail(path, e);
+        }
+    }
+    println!("🔓 Unpacked {} file(s) from \"{file}\".", files.len());
+}
+
+fn encrypt_stored() {
+    require_key();
+    let mut count = 0;
+    for path in stored_files() {
+        match encryption::seal(&path) {
+            Ok(encrypted) => count += usize::from(encrypted),
+            Err(e) => fail(&path, e),
+        }
+    }
+    println!("🔒 Encrypted {count} file(s).");
+}
+
+fn decrypt_stored() {
+    require_key();
+    let mut count = 0;
+    for path in stored_files() {
+        let data = fs::read(&path).unwrap_or_else(|e| fail(&path, e));
+        if !encryption::is_encrypted(&data) {
+            continue;
+        }
+        let contents = encryption::read(&path).unwrap_or_else(|e| fail(&path, e));
+        if let Err(e) = fs::write(&path, contents) {
+            fail(&path, e);
+        }
+        count += 1;
+    }
+    println!("🔓 Decrypted {count} file(s).");
+}
+
+pub fn handle(action: InputsAction, file: &str) {
+    match action {
+        InputsAction::Pack => pack(file),
+        InputsAction::Unpack => unpack(file),
+        InputsAction::Encrypt => encrypt_stored(),
+        InputsAction::Decrypt => decrypt_stored(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundles_files() {
+        let files = vec![
+            ("data/inputs/01.txt".to_string(), b"L68\nR48\n".to_vec()),
+            ("data/puzzles/01.md".to_string(), vec![]),
+        ];
+        let data = bundle(&files);
+        assert_eq!(unbundle(&data), Some(files));
+        assert_eq!(unbundle(&[]), Some(vec![]));
+        assert_eq!(unbundle(&data[..data.len() - 1]), None);
+    }
+
+    #[test]
+    fn only_unpacks_into_stored_directories() {
+        assert!(is_stored_path("data/inputs/01.txt"));
+        assert!(is_stored_path("data/puzzles/25.md"));
+        assert!(is_stored_path("data/inputs/01/alice.txt"));
+        assert!(!is_stored_path("data/inputs/01/../../x.txt"));
+        assert!(!is_stored_path("data/inputs/abc/x.txt"));
+        assert!(!is_stored_path("data/inputs/../../src/main.txt"));
+        assert!(!is_stored_path("data/inputs/01.md"));
+        assert!(!is_stored_path("data/examples/01.txt"));
+        assert!(!is_stored_path("src/bin/01.rs"));
+    }
+}
@@ -1,6 +1,14 @@
 pub mod all;
+pub mod asm;
+pub mod check;
+pub mod completions;
 pub mod download;
+pub mod generate;
+pub mod inputs;
+pub mod profile;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod watch;
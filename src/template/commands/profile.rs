@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use crate::template::commands::asm;
+use crate::template::run_multi::{child_commands, print_table};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, PART_FNS, registry};
+
+/// Hardware counters measured with `perf stat`, in the order they are shown.
+const EVENTS: [&str; 4] = ["cycles", "instructions", "cache-misses", "branch-misses"];
+
+/// Samples per second taken by `perf record` for the flamegraph.
+const SAMPLE_FREQUENCY: &str = "999";
+
+const FLAMEGRAPH_WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: usize = 16;
+
+fn perf_available() -> bool {
+    cfg!(target_os = "linux")
+        && Command::new("perf")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+}
+
+fn output_dir() -> PathBuf {
+    asm::target_dir().join("profile")
+}
+
+/// Runs the binary of a day under `perf` with `perf_args`, profiling `part` for `iterations`.
+fn run_perf(perf_args: &[&str], binary: &Path, part: u8, iterations: u64) -> Result<(), String> {
+    let status = Command::new("perf")
+        .args(perf_args)
+        .arg("--")
+        .arg(binary)
+        .args(["--profile", &part.to_string()])
+        .args(["--iterations", &iterations.to_string()])
+        .stdout(Stdio::null())
+        .status()
+        .map_err(|e| format!("failed to run perf: {e}"))?;
+    if !status.success() {
+        return Err(format!(
+            "`perf {}` failed, see its output above",
+            perf_args[0]
+        ));
+    }
+    Ok(())
+}
+
+/// How often a part runs so that profiling it takes about a second, the same as benching it.
+fn default_iterations(day: Day, part: u8) -> Result<u64, String> {
+    let output = child_commands::capture_solution(day, true, true, None, None)
+        .map_err(|e| format!("failed to time part {part}: {e}"))?;
+    let nanos =
+        child_commands::parse_part_nanos(&output)[usize::from(part - 1)].ok_or_else(|| {
+            format!("part {part} did not report a time, pass `--iterations` to profile it anyway")
+        })?;
+    Ok((1e9 / nanos.max(10.0)).clamp(10.0, 10_000.0) as u64)
+}
+
+/// Parses the counters of `perf stat -x ,`, summing the counters of hybrid CPUs such as
+/// `cpu_core/cycles/` and `cpu_atom/cycles/`. Counters that were not counted are left out.
+fn parse_perf_stat(output: &str) -> BTreeMap<String, u64> {
+    let mut counters = BTreeMap::new();
+    for line in output.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        let (Some(value), Some(event)) = (fields.first(), fields.get(2)) else {
+            continue;
+        };
+        let Ok(value) = value.parse::<u64>() else {
+            continue;
+        };
+        // `cpu_core/cycles:u/` or `cycles:u`.
+        let event = event
+            .split('/')
+            .find(|s| !s.is_empty() && !s.starts_with("cpu"));
+        let Some(event) = event.and_then(|e| e.split(':').next()) else {
+            continue;
+        };
+        *counters.entry(event.to_string()).or_default() += value;
+    }
+    counters
+}
+
+/// Counts the binary makes with `iterations` of the part.
+fn perf_stat(binary: &Path, part: u8, iterations: u64) -> Result<BTreeMap<String, u64>, String> {
+    let path = output_dir().join("stat.csv");
+    let path_str = path.to_string_lossy();
+    let events = EVENTS.join(",");
+    run_perf(
+        &["stat", "-x", ",", "-o", &path_str, "-e", &events],
+        binary,
+        part,
+        iterations,
+    )?;
+    let output = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&path);
+    Ok(parse_perf_stat(&output))
+}
+
+fn format_count(count: f64) -> String {
+    match count {
+        c if c >= 1e9 => format!("{:.2}G", c / 1e9),
+        c if c >= 1e6 => format!("{:.2}M", c / 1e6),
+        c if c >= 1e3 => format!("{:.2}k", c / 1e3),
+        c => format!("{c:.0}"),
+    }
+}
+
+/// Prints the counters of a part per iteration. The counts of a run without iterations, which
+/// only reads the input, are taken off first.
+fn print_counters(
+    profiled: &BTreeMap<String, u64>,
+    setup: &BTreeMap<String, u64>,
+    iterations: u64,
+) {
+    let per_iteration = |event: &str| {
+        let count = profiled.get(event)?;
+        let setup = setup.get(event).copied().unwrap_or(0);
+        Some(count.saturating_sub(setup) as f64 / iterations as f64)
+    };
+
+    let mut rows = vec![["Counter", "Per iteration", "Setup"].map(String::from)];
+    for event in EVENTS {
+        rows.push([
+            event.to_string(),
+            per_iteration(event).map_or("✖ not counted".into(), format_count),
+            setup
+                .get(event)
+                .map_or(String::new(), |&c| format_count(c as f64)),
+        ]);
+    }
+    print_table(&rows);
+
+    if let (Some(instructions), Some(cycles)) =
+        (per_iteration("instructions"), per_iteration("cycles"))
+        && cycles > 0.0
+    {
+        println!("Instructions per cycle: {:.2}", instructions / cycles);
+    }
+}
+
+/// Folds the samples printed by `perf script` into stacks like `main;_03::part_one;alloc`,
+/// with the number of samples of each. When `root` is on a stack, the frames below it are cut
+/// off so the flamegraph starts at the part. Stacks without it are dropped, unless no stack has
+/// it, e.g. because the part was inlined.
+fn fold_stacks(perf_script: &str, root: &str) -> BTreeMap<String, u64> {
+    let mut samples: Vec<Vec<&str>> = vec![];
+    for sample in perf_script.split("\n\n") {
+        // the first line describes the sample, the others are its frames from the innermost.
+        let mut frames: Vec<&str> = sample
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .skip(1)
+            .filter_map(|line| {
+                let (_address, symbol) = line.trim().split_once(' ')?;
+                let symbol = symbol.rfind(" (").map_or(symbol, |i| &symbol[..i]);
+                Some(symbol.rfind("+0x").map_or(symbol, |i| &symbol[..i]))
+            })
+            .collect();
+        frames.reverse();
+        if !frames.is_empty() {
+            samples.push(frames);
+        }
+    }
+
+    let has_root = samples.iter().any(|frames| frames.contains(&root));
+    let mut stacks = BTreeMap::new();
+    for frames in samples {
+        let frames = match frames.iter().position(|&frame| frame == root) {
+            Some(i) => &frames[i..],
+            None if has_root => continue,
+            None => &frames[..],
+        };
+        *stacks.entry(frames.join(";")).or_default() += 1;
+    }
+    stacks
+}
+
+#[derive(Default)]
+struct Frame {
+    samples: u64,
+    children: BTreeMap<String, Frame>,
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A warm colour that stays the same for a function name.
+fn frame_colour(name: &str) -> String {
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x100_0000_01b3)
+    });
+    format!(
+        "rgb({},{},{})",
+        205 + hash % 50,
+        (hash >> 8) % 180 + 40,
+        (hash >> 16) % 55
+    )
+}
+
+/// Size of the flamegraph, shared by all frames.
+struct Layout {
+    height: usize,
+    /// Pixels per sample.
+    scale: f64,
+    samples: u64,
+}
+
+fn depth(frame: &Frame) -> usize {
+    frame
+        .children
+        .values()
+        .map(|child| 1 + depth(child))
+        .max()
+        .unwrap_or(0)
+}
+
+fn draw(svg: &mut String, layout: &Layout, name: &str, frame: &Frame, x: f64, level: usize) {
+    let width = frame.samples as f64 * layout.scale;
+    let y = layout.height - (level + 1) * FRAME_HEIGHT;
+    let percent = frame.samples as f64 * 100.0 / layout.samples as f64;
+    let name = escape_xml(name);
+    // about 7 pixels per character of the 12px font.
+    let chars = ((width - 6.0) / 7.0).max(0.0) as usize;
+    let label = if name.chars().count() <= chars {
+        name.clone()
+    } else if chars > 2 {
+        format!("{}..", name.chars().take(chars - 2).collect::<String>())
+    } else {
+        String::new()
+    };
+    let _ = writeln!(
+        svg,
+        r#"<g><title>{name} ({} samples, {percent:.2}%)</title><rect x="{x:.2}" y="{y}" width="{width:.2}" height="{}" fill="{}" rx="2"/><text x="{:.2}" y="{}">{label}</text></g>"#,
+        frame.samples,
+        FRAME_HEIGHT - 1,
+        frame_colour(&name),
+        x + 3.0,
+        y + FRAME_HEIGHT - 4,
+    );
+
+    let mut child_x = x;
+    for (child_name, child) in &frame.children {
+        draw(svg, layout, child_name, child, child_x, level + 1);
+        child_x += child.samples as f64 * layout.scale;
+    }
+}
+
+/// Renders folded stacks as a flamegraph, with the outermost frame at the bottom and a frame as
+/// wide as its share of the samples. Hovering a frame shows its name and samples.
+fn flamegraph(stacks: &BTreeMap<String, u64>, title: &str) -> String {
+    let mut root = Frame::default();
+    for (stack, &samples) in stacks {
+        root.samples += samples;
+        let mut frame = &mut root;
+        for name in stack.split(';') {
+            frame = frame.children.entry(name.to_string()).or_default();
+            frame.samples += samples;
+        }
+    }
+
+    let header = 2 * FRAME_HEIGHT;
+    let height = header + (depth(&root) + 1) * FRAME_HEIGHT;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{FLAMEGRAPH_WIDTH}" height="{height}" font-family="monospace" font-size="12">
+<rect width="100%" height="100%" fill="rgb(250,250,240)"/>
+<text x="{}" y="{}" text-anchor="middle" font-size="16">{}</text>
+"#,
+        FLAMEGRAPH_WIDTH / 2.0,
+        FRAME_HEIGHT + 4,
+        escape_xml(title),
+    );
+    if root.samples > 0 {
+        let layout = Layout {
+            height,
+            scale: FLAMEGRAPH_WIDTH / root.samples as f64,
+            samples: root.samples,
+        };
+        draw(&mut svg, &layout, "all", &root, 0.0, 0);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Samples the part with `perf record` and writes a flamegraph of it.
+fn record_flamegraph(
+    binary: &Path,
+    day: Day,
+    part: u8,
+    iterations: u64,
+) -> Result<PathBuf, String> {
+    let data = output_dir().join(format!("{day}-part{part}.perf.data"));
+    let data_str = data.to_string_lossy();
+    // dwarf unwinding works with the unwind tables of the release build, without frame pointers.
+    run_perf(
+        &[
+            "record",
+            "--quiet",
+            "-F",
+            SAMPLE_FREQUENCY,
+            "--call-graph",
+            "dwarf",
+            "-o",
+            &data_str,
+        ],
+        binary,
+        part,
+        iterations,
+    )?;
+
+    let output = Command::new("perf")
+        .args(["script", "-i", &data_str])
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run perf script: {e}"))?;
+    let stacks = fold_stacks(
+        &String::from_utf8_lossy(&output.stdout),
+        &format!("_{day}::{}", PART_FNS[usize::from(part - 1)]),
+    );
+    if stacks.is_empty() {
+        return Err("perf did not record any samples".into());
+    }
+
+    let path = output_dir().join(format!("{day}-part{part}.svg"));
+    let title = format!("Day {day} part {part}, {iterations} iteration(s)");
+    fs::write(&path, flamegraph(&stacks, &title))
+        .map_err(|e| format!("failed to write \"{}\": {e}", path.display()))?;
+    Ok(path)
+}
+
+fn fail(e: impl std::fmt::Display) -> ! {
+    eprintln!("Error: {e}.");
+    process::exit(1);
+}
+
+pub fn handle(day: Day, part: Option<u8>, iterations: Option<u64>, flamegraph: bool) {
+    let Some(info) = registry::get(day) else {
+        fail(format!("day {day} is not scaffolded"));
+    };
+    if !perf_available() {
+        fail(
+            "profiling needs `perf` on Linux, e.g. from the linux-tools package of your distribution",
+        );
+    }
+    let binary = asm::build_release(day).unwrap_or_else(|e| fail(e));
+    fs::create_dir_all(output_dir()).unwrap_or_else(|e| fail(e));
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    let parts = part.map_or(info.parts.clone(), |part| vec![part]);
+    for (i, &part) in parts.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let iterations = iterations
+            .map_or_else(|| default_iterations(day, part), Ok)
+            .unwrap_or_else(|e| fail(e))
+            .max(1);
+        println!("Part {part} · {iterations} iteration(s)");
+
+        let setup = perf_stat(&binary, part, 0).unwrap_or_else(|e| fail(e));
+        let profiled = perf_stat(&binary, part, iterations).unwrap_or_else(|e| fail(e));
+        print_counters(&profiled, &setup, iterations);
+
+        if flamegraph {
+            let path =
+                record_flamegraph(&binary, day, part, iterations).unwrap_or_else(|e| fail(e));
+            println!("Wrote the flamegraph to \"{}\".", path.display());
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_perf_stat() {
+        let output = "# started on Mon Dec  1 06:00:00 2025
+
+1203456,,cpu_core/cycles:u/,410000,100.00,,
+1000,,cpu_atom/cycles:u/,410000,100.00,,
+2500000,,instructions:u,410000,100.00,3.20,insn per cycle
+<not supported>,,cache-misses:u,0,100.00,,
+1234,,branch-misses:u,410000,100.00,,
+";
+        let counters = parse_perf_stat(output);
+        assert_eq!(counters["cycles"], 1_204_456);
+        assert_eq!(counters["instructions"], 2_500_000);
+        assert_eq!(counters["branch-misses"], 1234);
+        assert!(!counters.contains_key("cache-misses"));
+    }
+
+    #[test]
+    fn folds_perf_script_stacks() {
+        let output = "03 4242 1.000001:     1001 cycles:u:
+\t    55d0c1a2b3c4 _03::max_joltage+0x44 (/root/crate/target/release/03)
+\t    55d0c1a2b3d0 _03::part_one+0x10 (/root/crate/target/release/03)
+\t    55d0c1a2b3e0 main+0x20 (/root/crate/target/release/03)
+
+03 4242 1.000002:     1001 cycles:u:
+\t    55d0c1a2b3d0 _03::part_one+0x12 (/root/crate/target/release/03)
+\t    55d0c1a2b3e0 main+0x20 (/root/crate/target/release/03)
+
+03 4242 1.000003:     1001 cycles:u:
+\t    7f0000000000 [unknown] ([unknown])
+\t    55d0c1a2b3e0 advent_of_code::template::read_input+0x8 (/root/crate/target/release/03)
+";
+        let stacks = fold_stacks(output, "_03::part_one");
+        assert_eq!(
+            stacks.into_iter().collect::<Vec<_>>(),
+            [
+                ("_03::part_one".to_string(), 1),
+                ("_03::part_one;_03::max_joltage".to_string(), 1),
+            ]
+        );
+
+        let stacks = fold_stacks(output, "_03::part_two");
+        assert_eq!(stacks.len(), 3);
+        assert_eq!(stacks["advent_of_code::template::read_input;[unknown]"], 1);
+    }
+
+    #[test]
+    fn renders_flamegraphs() {
+        let stacks = BTreeMap::from([
+            ("_03::part_one".to_string(), 1),
+            ("_03::part_one;<Lines as Iterator>::next".to_string(), 3),
+        ]);
+        let svg = flamegraph(&stacks, "Day 03 part 1");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<g>").count(), 3);
+        assert!(svg.contains("<title>&lt;Lines as Iterator&gt;::next (3 samples, 75.00%)</title>"));
+        assert!(svg.contains(r#"<rect x="0.00" y="64" width="1200.00""#));
+    }
+}
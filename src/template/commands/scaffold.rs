@@ -1,79 +1,317 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{fs, path::Path, process};
 
-use crate::template::Day;
+use crate::template::cli::{Existing, ExistingPolicy};
+use crate::template::commands::download::{self, Examples};
+use crate::template::registry::get_path_for_bin;
+use crate::template::{Day, aoc_cli, puzzle};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
+/// Directory holding the named templates selectable with `--template <name>`.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Values substituted into a module template.
+struct Placeholders {
+    day: Day,
+    year: Option<u16>,
+    title: Option<String>,
+    /// Expected example answers, as written in the puzzle.
+    part_one_answer: Option<String>,
+    part_two_answer: Option<String>,
+    /// Whether part two has an example of its own in `data/examples/NN-2.txt`.
+    part_two_example: bool,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+impl Placeholders {
+    fn new(day: Day, examples: &Examples, part_two_example: bool) -> Self {
+        Self {
+            day,
+            year: aoc_cli::get_year(),
+            title: puzzle::read_puzzle(day).as_deref().and_then(puzzle::title),
+            part_one_answer: examples.part_one_answer.clone(),
+            part_two_answer: examples.part_two_answer.clone(),
+            part_two_example,
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        let title = self.title.as_deref().unwrap_or_default();
+        // e.g. the `string-answer` template, whose tests compare with `Some("...")`.
+        let string_answers = template.contains("-> Option<String>");
+        let answer =
+            |answer: &Option<String>| puzzle::answer_expr(answer.as_deref(), string_answers);
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace(
+                "%YEAR%",
+                &self.year.map(|year| year.to_string()).unwrap_or_default(),
+            )
+            // inside a string literal, the title must be escaped to keep the module compiling.
+            .replace("\"%TITLE%\"", &format!("{title:?}"))
+            .replace("%TITLE%", title)
+            .replace("%PART_ONE_ANSWER%", &answer(&self.part_one_answer))
+            .replace("%PART_TWO_ANSWER%", &answer(&self.part_two_answer))
+            .replace(
+                "%PART_TWO_EXAMPLE%",
+                if self.part_two_example {
+                    r#"read_file_part("examples", DAY, 2)"#
+                } else {
+                    r#"read_file("examples", DAY)"#
+                },
+            )
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Names of the templates in [`TEMPLATES_DIR`].
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_suffix(".txt").map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    names
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+/// Loads a named template, falling back to the built-in one when no name is given.
+fn load_template(name: Option<&str>) -> String {
+    let Some(name) = name else {
+        return MODULE_TEMPLATE.to_string();
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+    match fs::read_to_string(format!("{TEMPLATES_DIR}/{name}.txt")) {
+        Ok(template) => template,
+        Err(_) => {
+            eprintln!(
+                "Template \"{name}\" not found in \"{TEMPLATES_DIR}/\". Available templates: {}",
+                available_templates().join(", ")
+            );
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+/// A file created by `scaffold`.
+struct Artefact {
+    path: String,
+    contents: String,
+    existing: Existing,
+}
+
+/// What happened, or with `--dry-run` would happen, to an [`Artefact`].
+enum Outcome {
+    Created,
+    Unchanged,
+    Skipped,
+    Overwritten,
+    BackedUp(String),
+}
+
+/// The first of `path.bak`, `path.bak.1`, ... that does not exist yet.
+fn backup_path(path: &str) -> String {
+    (0..)
+        .map(|i| match i {
+            0 => format!("{path}.bak"),
+            i => format!("{path}.bak.{i}"),
+        })
+        .find(|candidate| !Path::new(candidate).exists())
+        .unwrap()
+}
+
+impl Artefact {
+    fn new(path: String, contents: String, existing: Existing) -> Self {
+        Self {
+            path,
+            contents,
+            existing,
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    }
+
+    /// An empty placeholder for the input. It is only ever created, so an input that is already
+    /// there, e.g. just fetched with `--download`, is kept whatever the `--existing` policy.
+    fn input(path: String) -> Self {
+        Self::new(path, String::new(), Existing::Skip)
+    }
+
+    /// Decides what to do with this artefact. Empty files count as missing.
+    fn plan(&self) -> Outcome {
+        let Ok(current) = fs::read(&self.path) else {
+            return Outcome::Created;
+        };
+        if current == self.contents.as_bytes() {
+            Outcome::Unchanged
+        } else if current.is_empty() {
+            Outcome::Created
+        } else {
+            match self.existing {
+                Existing::Skip => Outcome::Skipped,
+                Existing::Overwrite => Outcome::Overwritten,
+                Existing::Backup => Outcome::BackedUp(backup_path(&self.path)),
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    fn apply(&self, outcome: &Outcome) -> Result<(), std::io::Error> {
+        match outcome {
+            Outcome::Unchanged | Outcome::Skipped => Ok(()),
+            Outcome::Created | Outcome::Overwritten => fs::write(&self.path, &self.contents),
+            Outcome::BackedUp(backup) => {
+                fs::rename(&self.path, backup)?;
+                fs::write(&self.path, &self.contents)
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    }
+}
+
+fn print_summary(results: &[(&Artefact, Outcome)], dry_run: bool) {
+    for (artefact, outcome) in results {
+        let path = &artefact.path;
+        let line = match (outcome, dry_run) {
+            (Outcome::Created, false) => format!("Created \"{path}\""),
+            (Outcome::Created, true) => format!("Would create \"{path}\""),
+            (Outcome::Unchanged, _) => format!("Unchanged \"{path}\""),
+            (Outcome::Skipped, false) => format!("Skipped existing \"{path}\""),
+            (Outcome::Skipped, true) => format!("Would skip existing \"{path}\""),
+            (Outcome::Overwritten, false) => format!("Overwrote \"{path}\""),
+            (Outcome::Overwritten, true) => format!("Would overwrite \"{path}\""),
+            (Outcome::BackedUp(backup), false) => {
+                format!("Replaced \"{path}\", backup at \"{backup}\"")
+            }
+            (Outcome::BackedUp(backup), true) => {
+                format!("Would replace \"{path}\", backing up to \"{backup}\"")
+            }
+        };
+        println!("{line}");
+    }
+}
+
+pub fn handle(
+    day: Day,
+    existing: ExistingPolicy,
+    template: Option<&str>,
+    example: Option<usize>,
+    dry_run: bool,
+) {
+    // only ask for the example when it could be written, so the user is not asked in vain.
+    let example_path = format!("data/examples/{day}.txt");
+    let has_example = fs::metadata(&example_path).is_ok_and(|m| m.len() > 0);
+    let keep_examples = existing.example == Existing::Skip && has_example;
+    let mut examples = download::extract_examples(day, example, !dry_run && !keep_examples);
+    if keep_examples {
+        examples.files.clear();
+    }
+    if examples.files.is_empty() {
+        examples.files.push((example_path, String::new()));
+    }
+
+    let part_two_path = format!("data/examples/{day}-2.txt");
+    let part_two_example = examples
+        .files
+        .iter()
+        .any(|(path, _)| *path == part_two_path)
+        || fs::metadata(&part_two_path).is_ok_and(|m| m.len() > 0);
+    let module =
+        Placeholders::new(day, &examples, part_two_example).render(&load_template(template));
+
+    let mut artefacts = vec![
+        Artefact::new(get_path_for_bin(day), module, existing.module),
+        Artefact::input(format!("data/inputs/{day}.txt")),
+    ];
+    artefacts.extend(
+        examples
+            .files
+            .into_iter()
+            .map(|(path, contents)| Artefact::new(path, contents, existing.example)),
+    );
+
+    let mut results = vec![];
+    for artefact in &artefacts {
+        let outcome = artefact.plan();
+        if !dry_run && let Err(e) = artefact.apply(&outcome) {
+            eprintln!("Failed to write \"{}\": {e}", artefact.path);
             process::exit(1);
         }
+        results.push((artefact, outcome));
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    print_summary(&results, dry_run);
+
+    if !dry_run {
+        println!("---");
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{Artefact, Outcome, Placeholders};
+
+    #[test]
+    fn keeps_downloaded_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt").to_string_lossy().into_owned();
+
+        // as after `scaffold 1 --download --existing overwrite`, which fetches the input first.
+        fs::write(&path, "1 2 3\n").unwrap();
+        let input = Artefact::input(path.clone());
+        let outcome = input.plan();
+        assert!(matches!(outcome, Outcome::Skipped));
+        input.apply(&outcome).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(input.plan(), Outcome::Created));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn escapes_titles_in_string_literals() {
+        let placeholders = Placeholders {
+            day: crate::day!(4),
+            year: Some(2025),
+            title: Some(r#"The "Quote" \ Slash"#.into()),
+            part_one_answer: None,
+            part_two_answer: None,
+            part_two_example: false,
+        };
+        assert_eq!(
+            placeholders.render("//! %TITLE%\nsolution!(4, title = \"%TITLE%\");"),
+            r#"//! The "Quote" \ Slash
+solution!(4, title = "The \"Quote\" \\ Slash");"#
+        );
+    }
+
+    #[test]
+    fn reads_the_part_two_example_and_answers() {
+        let template = "part_two(&%PART_TWO_EXAMPLE%), %PART_ONE_ANSWER%, %PART_TWO_ANSWER%";
+        let mut placeholders = Placeholders {
+            day: crate::day!(4),
+            year: None,
+            title: None,
+            part_one_answer: Some("142".into()),
+            part_two_answer: None,
+            part_two_example: false,
+        };
+        assert_eq!(
+            placeholders.render(template),
+            r#"part_two(&read_file("examples", DAY)), Some(142), None"#
+        );
+
+        placeholders.part_two_answer = Some("281".into());
+        placeholders.part_two_example = true;
+        assert_eq!(
+            placeholders.render(template),
+            r#"part_two(&read_file_part("examples", DAY, 2)), Some(142), Some(281)"#
+        );
+    }
 }
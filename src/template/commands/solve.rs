@@ -1,20 +1,34 @@
 use std::process::{Command, Stdio};
 
 use crate::template::Day;
+use crate::template::cli::Visualize;
+use crate::template::run_multi::run_each_input;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<&str>,
+    visualize: Option<(Visualize, u32)>,
+    debug: bool,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
+    // `debug!` is compiled out of optimised builds unless the feature is enabled.
+    let mut features = vec![];
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
     } else if release {
         cmd_args.push("--release".to_string());
     }
+    if debug && (dhat || release) {
+        features.push("debug-log");
+    }
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
 
     cmd_args.push("--".to_string());
 
@@ -23,6 +37,24 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    if debug {
+        cmd_args.push("--debug".to_string());
+    }
+
+    if let Some((mode, fps)) = visualize {
+        cmd_args.extend([
+            "--visualize".to_string(),
+            mode.as_arg().to_string(),
+            "--fps".to_string(),
+            fps.to_string(),
+        ]);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +64,8 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Runs the days against every one of their inputs and compares the answers.
+pub fn handle_each_input(days: &[Day], release: bool) {
+    run_each_input(days, release, false);
+}
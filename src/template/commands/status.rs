@@ -0,0 +1,254 @@
+use std::{
+    fs,
+    io::{self, Write},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::template::registry;
+use crate::template::run_multi::child_commands;
+use crate::template::timings::Timings;
+use crate::template::{
+    ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW, Day, puzzle,
+};
+
+const TITLE_WIDTH: usize = 24;
+
+/// A table cell: coloured text and the width it takes up on screen.
+struct Cell {
+    text: String,
+    width: usize,
+}
+
+impl Cell {
+    fn new(text: &str, colour: &str) -> Self {
+        Self {
+            text: format!("{colour}{text}{ANSI_RESET}"),
+            width: text.chars().count(),
+        }
+    }
+
+    fn plain(text: &str) -> Self {
+        Self::new(text, "")
+    }
+
+    fn yes() -> Self {
+        Self::new("✔", ANSI_GREEN)
+    }
+
+    fn no() -> Self {
+        Self::new("✖", ANSI_RED)
+    }
+
+    fn unknown() -> Self {
+        Self::new("?", ANSI_YELLOW)
+    }
+
+    fn none() -> Self {
+        Self::new("·", ANSI_DIM)
+    }
+
+    fn check(ok: bool) -> Self {
+        if ok { Self::yes() } else { Self::no() }
+    }
+
+    /// One cell per part, separated by a space.
+    fn parts(cells: [Cell; 2]) -> Self {
+        let [one, two] = cells;
+        Self {
+            text: format!("{} {}", one.text, two.text),
+            width: one.width + 1 + two.width,
+        }
+    }
+}
+
+/// Results of building and running a day, gathered with `--check`.
+struct Check {
+    tests_pass: bool,
+    /// Answers printed by the solution, `None` for parts that returned `None`.
+    answers: [Option<String>; 2],
+}
+
+/// Whether `path` exists and has contents.
+fn has_contents(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// Runs the tests and the solution of a day, capturing the output.
+fn check(day: Day, has_input: bool) -> Check {
+    let bin = day.to_string();
+    let tests_pass = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &bin])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+
+    let answers = if has_input {
+        match child_commands::capture_solution(day, false, true, None, None) {
+            Ok(output) => child_commands::parse_answers(&output),
+            Err(e) => {
+                eprintln!("\r\x1b[2KDay {day} failed: {e}");
+                [None, None]
+            }
+        }
+    } else {
+        [None, None]
+    };
+
+    Check {
+        tests_pass,
+        answers,
+    }
+}
+
+fn row(day: Day, timings: &Timings, run_checks: bool) -> Vec<Cell> {
+    let info = registry::get(day);
+    let markdown = puzzle::read_puzzle(day);
+    let submitted = markdown
+        .as_deref()
+        .map(puzzle::submitted_answers)
+        .unwrap_or_default();
+    let title = info
+        .and_then(|info| info.title.clone())
+        .or_else(|| markdown.as_deref().and_then(puzzle::title))
+        .unwrap_or_default();
+    let has_input = has_contents(&format!("data/inputs/{day}.txt"));
+
+    let mut cells = vec![
+        Cell::new(&day.to_string(), ANSI_BOLD),
+        Cell::plain(&title.chars().take(TITLE_WIDTH).collect::<String>()),
+        Cell::check(info.is_some()),
+        Cell::check(has_input),
+        Cell::check(has_contents(&format!("data/examples/{day}.txt"))),
+    ];
+
+    let Some(info) = info else {
+        cells.extend([
+            Cell::parts([Cell::none(), Cell::none()]),
+            Cell::none(),
+            Cell::parts([Cell::none(), Cell::none()]),
+        ]);
+        return finish_row(cells, day, timings, &submitted);
+    };
+
+    let checked = run_checks.then(|| {
+        eprint!("\rChecking day {day}...");
+        let _ = io::stderr().flush();
+        check(day, has_input)
+    });
+
+    match &checked {
+        None => {
+            let implemented = |part: u8| {
+                if info.parts.contains(&part) {
+                    Cell::yes()
+                } else {
+                    Cell::none()
+                }
+            };
+            cells.extend([
+                Cell::parts([implemented(1), implemented(2)]),
+                Cell::unknown(),
+                Cell::parts([Cell::unknown(), Cell::unknown()]),
+            ]);
+        }
+        Some(checked) => {
+            let returned = |part: usize| match (&checked.answers[part], has_input) {
+                (Some(_), _) => Cell::yes(),
+                (None, true) => Cell::no(),
+                (None, false) => Cell::unknown(),
+            };
+            let correct = |part: usize| match (&checked.answers[part], submitted.get(part)) {
+                (_, None) => Cell::none(),
+                (Some(answer), _) if answer == "▼" => Cell::unknown(),
+                (Some(answer), Some(expected)) => Cell::check(answer == expected),
+                (None, Some(_)) if has_input => Cell::no(),
+                (None, Some(_)) => Cell::unknown(),
+            };
+            cells.extend([
+                Cell::parts([returned(0), returned(1)]),
+                Cell::check(checked.tests_pass),
+                Cell::parts([correct(0), correct(1)]),
+            ]);
+        }
+    }
+
+    finish_row(cells, day, timings, &submitted)
+}
+
+/// Appends the benchmark and submission cells.
+fn finish_row(
+    mut cells: Vec<Cell>,
+    day: Day,
+    timings: &Timings,
+    submitted: &[String],
+) -> Vec<Cell> {
+    let best = timings
+        .data
+        .iter()
+        .filter(|timing| timing.day == day && timing.total_nanos > 0.0)
+        .map(|timing| timing.total_nanos)
+        .reduce(f64::min);
+    cells.push(match best {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(nanos) => Cell::plain(&format!("{:.1?}", Duration::from_nanos(nanos as u64))),
+        None => Cell::none(),
+    });
+
+    cells.push(match submitted.len() {
+        0 => Cell::none(),
+        stars => Cell::new(&"★".repeat(stars), ANSI_YELLOW),
+    });
+    cells
+}
+
+fn print_row(cells: &[Cell], widths: &[usize]) {
+    let line: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| {
+            let padding = width.saturating_sub(cell.width);
+            format!("{}{}", cell.text, " ".repeat(padding))
+        })
+        .collect();
+    println!("{}", line.join("  ").trim_end());
+}
+
+pub fn handle(days: &[Day], run_checks: bool) {
+    let timings = Timings::read_from_file();
+    let rows: Vec<Vec<Cell>> = days
+        .iter()
+        .map(|&day| row(day, &timings, run_checks))
+        .collect();
+    if run_checks {
+        eprint!("\r\x1b[2K");
+    }
+
+    let header: Vec<Cell> = [
+        "Day", "Title", "Module", "Input", "Example", "Parts", "Tests", "Answers", "Best", "Stars",
+    ]
+    .iter()
+    .map(|name| Cell::new(name, ANSI_BOLD))
+    .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .chain([&header])
+                .map(|cells| cells[i].width)
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    print_row(&header, &widths);
+    for cells in &rows {
+        print_row(cells, &widths);
+    }
+
+    if !run_checks {
+        println!();
+        println!("Run with `--check` to run the tests and verify the answers of each day.");
+    }
+}
@@ -1,27 +1,30 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::run_multi::{run_each_input, run_multi, run_sizes};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    days: &[Day],
+    store: bool,
+    input: Option<&str>,
+    each_input: bool,
+    sizes: Option<&[usize]>,
+    seed: u64,
+) {
+    if each_input {
+        run_each_input(days, true, true);
+        return;
+    }
+    if let Some(sizes) = sizes {
+        run_sizes(days, sizes, seed);
+        return;
+    }
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let stored_timings = Timings::read_from_file();
+    let days_to_run: HashSet<Day> = days.iter().copied().collect();
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, input).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -0,0 +1,112 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::registry::{self, get_path_for_bin};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, named_inputs};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of every file that affects a day, in a stable order.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// The solution module, the input, the named inputs in `data/inputs/NN/` and all example files
+/// (`NN.txt`, `NN-2.txt`, ...) of a day.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(get_path_for_bin(day)),
+        PathBuf::from(format!("data/inputs/{day}.txt")),
+    ];
+    files.extend(named_inputs(day).into_iter().map(|(_, path)| path));
+
+    let mut examples: Vec<PathBuf> = fs::read_dir("data/examples")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".txt"))
+                .is_some_and(|stem| stem == day.to_string() || stem.starts_with(&format!("{day}-")))
+        })
+        .collect();
+    examples.sort();
+    files.extend(examples);
+
+    files
+}
+
+fn snapshot(day: Day) -> Snapshot {
+    watched_files(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Runs a cargo command with inherited output, so compile errors show up as usual.
+fn cargo(args: &[&str]) -> bool {
+    Command::new("cargo")
+        .args(args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn run(day: Day, release: bool) {
+    print!("{CLEAR_SCREEN}");
+    println!(
+        "{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, Ctrl-C to stop){ANSI_RESET}"
+    );
+    println!("------");
+
+    let bin = day.to_string();
+    let mut test_args = vec!["test", "--quiet", "--bin", &bin];
+    let mut run_args = vec!["run", "--quiet", "--bin", &bin];
+    if release {
+        test_args.push("--release");
+        run_args.push("--release");
+    }
+
+    if !cargo(&test_args) {
+        println!("\n{ANSI_BOLD}Tests failed.{ANSI_RESET} Waiting for changes...");
+        return;
+    }
+
+    println!();
+    if !cargo(&run_args) {
+        println!("\n{ANSI_BOLD}Solution failed.{ANSI_RESET} Waiting for changes...");
+    }
+}
+
+pub fn handle(day: Day, release: bool) {
+    if !registry::is_scaffolded(day) {
+        eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    let mut last = snapshot(day);
+    run(day, release);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(day);
+        if current == last {
+            continue;
+        }
+
+        // editors often write a file in several steps, wait for them to settle.
+        thread::sleep(POLL_INTERVAL);
+        last = snapshot(day);
+        run(day, release);
+    }
+}
@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::registry;
+use crate::template::timings::Timings;
+use crate::template::{Day, DayFromStrError, all_days};
+
+/// A selection of days as given on the command line.
+///
+/// A selector is a comma separated list of items, each of which is one of:
+///  - a single day, e.g. `3`
+///  - an inclusive range, e.g. `1-5`
+///  - `all`: every day of advent
+///  - `unsolved`: scaffolded days without stored timings for both parts
+///  - `last`: the latest scaffolded day
+///
+/// For example, `1-3,9` selects days 1, 2, 3 and 9.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelector(Vec<Item>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Item {
+    Range(Day, Day),
+    All,
+    Unsolved,
+    Last,
+}
+
+impl DaySelector {
+    /// Selects every day of advent.
+    pub fn all() -> Self {
+        Self(vec![Item::All])
+    }
+
+    /// Selects scaffolded days that are not fully benchmarked yet.
+    pub fn unsolved() -> Self {
+        Self(vec![Item::Unsolved])
+    }
+
+    /// The selected days in ascending order, looking at the registry and the stored timings to
+    /// resolve `unsolved` and `last`.
+    pub fn resolve(&self) -> Vec<Day> {
+        let timings = Timings::read_from_file();
+        self.resolve_with(registry::is_scaffolded, |day| timings.is_day_complete(day))
+    }
+
+    /// Like [`DaySelector::resolve`], with the state of each day provided by the caller.
+    pub fn resolve_with(
+        &self,
+        is_scaffolded: impl Fn(Day) -> bool,
+        is_complete: impl Fn(Day) -> bool,
+    ) -> Vec<Day> {
+        all_days()
+            .filter(|&day| {
+                self.0.iter().any(|item| match *item {
+                    Item::Range(first, last) => first <= day && day <= last,
+                    Item::All => true,
+                    Item::Unsolved => is_scaffolded(day) && !is_complete(day),
+                    Item::Last => all_days()
+                        .filter(|&d| is_scaffolded(d))
+                        .last()
+                        .is_some_and(|last| last == day),
+                })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for DaySelector {
+    type Err = DaySelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |value: &str| {
+            Day::from_str(value).map_err(|e| DaySelectorError::InvalidDay(value.into(), e))
+        };
+
+        let items = s
+            .split(',')
+            .map(str::trim)
+            .map(|item| match item {
+                "" => Err(DaySelectorError::Empty),
+                "all" => Ok(Item::All),
+                "unsolved" => Ok(Item::Unsolved),
+                "last" => Ok(Item::Last),
+                _ => match item.split_once('-') {
+                    Some((first, last)) => {
+                        let (first, last) = (parse_day(first.trim())?, parse_day(last.trim())?);
+                        if first > last {
+                            return Err(DaySelectorError::ReversedRange(first, last));
+                        }
+                        Ok(Item::Range(first, last))
+                    }
+                    None => parse_day(item).map(|day| Item::Range(day, day)),
+                },
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self(items))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelector`].
+#[derive(Debug)]
+pub enum DaySelectorError {
+    Empty,
+    InvalidDay(String, DayFromStrError),
+    ReversedRange(Day, Day),
+}
+
+impl Error for DaySelectorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DaySelectorError::InvalidDay(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DaySelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySelectorError::Empty => f.write_str("empty day selection"),
+            DaySelectorError::InvalidDay(value, e) => write!(f, "`{value}` is not a day, {e}"),
+            DaySelectorError::ReversedRange(first, last) => {
+                write!(f, "range {first}-{last} ends before it starts")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySelector, DaySelectorError};
+    use crate::day;
+
+    fn resolve(s: &str) -> Vec<u8> {
+        // Days 1 to 9 are scaffolded, and only 1 to 8 are complete.
+        s.parse::<DaySelector>()
+            .unwrap()
+            .resolve_with(|day| day <= 9, |day| day <= 8)
+            .into_iter()
+            .map(|day| day.into_inner())
+            .collect()
+    }
+
+    #[test]
+    fn parses_days_ranges_and_lists() {
+        assert_eq!(resolve("3"), vec![3]);
+        assert_eq!(resolve("1-5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(resolve("9,1,4"), vec![1, 4, 9]);
+        assert_eq!(resolve("2-3, 3-4"), vec![2, 3, 4]);
+        assert_eq!(resolve("1-3,9"), vec![1, 2, 3, 9]);
+        assert_eq!(resolve("all").len(), 25);
+    }
+
+    #[test]
+    fn resolves_keywords() {
+        assert_eq!(resolve("unsolved"), vec![9]);
+        assert_eq!(resolve("last"), vec![9]);
+        assert_eq!(resolve("1,last"), vec![1, 9]);
+        assert!(
+            DaySelector::unsolved()
+                .resolve_with(|_| false, |_| false)
+                .is_empty()
+        );
+        assert!(
+            DaySelector::all()
+                .resolve_with(|_| false, |_| false)
+                .contains(&day!(25))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        assert!(matches!(
+            "".parse::<DaySelector>(),
+            Err(DaySelectorError::Empty)
+        ));
+        assert!(matches!(
+            "1,,2".parse::<DaySelector>(),
+            Err(DaySelectorError::Empty)
+        ));
+        assert!(matches!(
+            "0-3".parse::<DaySelector>(),
+            Err(DaySelectorError::InvalidDay(..))
+        ));
+        assert!(matches!(
+            "5-2".parse::<DaySelector>(),
+            Err(DaySelectorError::ReversedRange(..))
+        ));
+        assert!(matches!(
+            "first".parse::<DaySelector>(),
+            Err(DaySelectorError::InvalidDay(..))
+        ));
+    }
+}
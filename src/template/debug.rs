@@ -0,0 +1,74 @@
+/// Runtime switch for the [`debug!`](crate::debug) macro.
+/// Messages are printed when the binary runs with `--debug` (e.g. `cargo solve 9 --debug`) or
+/// [`DEBUG_VAR`] is set, except while the runner benchmarks a part.
+use std::env;
+use std::fmt::Arguments;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::template::{ANSI_DIM, ANSI_RESET};
+
+/// Environment variable that turns on debug messages, e.g. for `cargo test`.
+pub const DEBUG_VAR: &str = "AOC_DEBUG";
+
+static SUPPRESSED: AtomicBool = AtomicBool::new(false);
+
+/// Whether debug messages are printed right now.
+pub fn enabled() -> bool {
+    static REQUESTED: OnceLock<bool> = OnceLock::new();
+    let requested = *REQUESTED.get_or_init(|| {
+        env::args().any(|arg| arg == "--debug")
+            || env::var(DEBUG_VAR).is_ok_and(|value| !value.is_empty() && value != "0")
+    });
+    requested && !SUPPRESSED.load(Ordering::Relaxed)
+}
+
+/// Silences debug messages, e.g. while benchmarking a part thousands of times.
+pub fn set_suppressed(suppressed: bool) {
+    SUPPRESSED.store(suppressed, Ordering::Relaxed);
+}
+
+#[doc(hidden)]
+pub fn print(args: Arguments) {
+    eprintln!("{ANSI_DIM}[debug]{ANSI_RESET} {args}");
+}
+
+/// Prints a debug message to stderr, formatted like `println!`, when the solution runs with
+/// `--debug` or `AOC_DEBUG` is set.
+///
+/// Release builds compile the message out unless they enable the `debug-log` feature, which
+/// `cargo solve <day> --release --debug` does.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        // `cfg!` is evaluated in the solution's crate. The arguments are still type checked
+        // when it is false, so variables that are only logged do not become unused.
+        if cfg!(any(debug_assertions, feature = "debug-log"))
+            && $crate::template::debug::enabled()
+        {
+            $crate::template::debug::print(format_args!($($arg)*));
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DEBUG_VAR, enabled, set_suppressed};
+
+    #[test]
+    fn is_off_unless_requested() {
+        // the test binary runs without `--debug`, and the macro must accept any format arguments.
+        let tiles = [(1, 2)];
+        crate::debug!("tiles: {tiles:?}");
+        set_suppressed(false);
+        assert_eq!(
+            enabled(),
+            std::env::var(DEBUG_VAR).is_ok_and(|v| !v.is_empty() && v != "0")
+        );
+        set_suppressed(true);
+        assert!(!enabled());
+        set_suppressed(false);
+    }
+}
@@ -0,0 +1,235 @@
+/// Optional encryption of puzzle inputs and descriptions at rest.
+/// When [`KEY_VAR`] is set, files written by `download` and `inputs unpack` are encrypted with a
+/// key derived from it, and files read by `read_file` or the commands are decrypted on the fly.
+/// Plain files keep working either way, so encryption can be turned on at any time.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::{env, fs, io, path::Path};
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::{Digest, Sha256};
+
+/// Environment variable holding the passphrase the key is derived from.
+pub const KEY_VAR: &str = "AOC_INPUTS_KEY";
+
+/// Marks the start of an encrypted file, followed by the salt, the nonce and the ciphertext.
+const MAGIC: &[u8] = b"aoc-encrypted-v1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Random bytes stored with each encrypted file and mixed into its key.
+pub type Salt = [u8; SALT_LEN];
+
+/// A passphrase, from which a key for ChaCha20-Poly1305 is derived for each salt. Deriving is
+/// slow on purpose, so the keys are cached.
+#[derive(Clone)]
+pub struct Key {
+    passphrase: String,
+    derived: Arc<Mutex<HashMap<Salt, [u8; 32]>>>,
+}
+
+impl Key {
+    pub fn new(passphrase: &str) -> Self {
+        Self {
+            passphrase: passphrase.to_string(),
+            derived: Arc::default(),
+        }
+    }
+
+    /// The key derived from [`KEY_VAR`], if it is set and not empty.
+    pub fn from_env() -> Option<Self> {
+        static CACHED: Mutex<Option<Key>> = Mutex::new(None);
+
+        let passphrase = env::var(KEY_VAR).ok().filter(|p| !p.is_empty())?;
+        let mut cached = CACHED.lock().unwrap_or_else(PoisonError::into_inner);
+        match cached.as_ref() {
+            Some(key) if key.passphrase == passphrase => Some(key.clone()),
+            _ => Some(cached.insert(Self::new(&passphrase)).clone()),
+        }
+    }
+
+    fn derive(&self, salt: &Salt) -> [u8; 32] {
+        let mut derived = self.derived.lock().unwrap_or_else(PoisonError::into_inner);
+        *derived.entry(*salt).or_insert_with(|| {
+            // Argon2id with the recommended default parameters.
+            let mut key = [0; 32];
+            Argon2::default()
+                .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+                .expect("the salt and key lengths are valid");
+            key
+        })
+    }
+}
+
+fn cipher(key: [u8; 32]) -> ChaCha20Poly1305 {
+    ChaCha20Poly1305::new(&key.into())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncryptionError {
+    MissingKey,
+    WrongKey,
+}
+
+impl Error for EncryptionError {}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::MissingKey => {
+                write!(f, "the file is encrypted, set {KEY_VAR} to decrypt it")
+            }
+            EncryptionError::WrongKey => write!(
+                f,
+                "the file could not be decrypted, {KEY_VAR} does not match the key it was encrypted with"
+            ),
+        }
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// A new, random salt.
+pub fn new_salt() -> Salt {
+    let mut salt = Salt::default();
+    getrandom::fill(&mut salt).expect("the system has no source of randomness");
+    salt
+}
+
+/// The salt of an encrypted file, if it has one.
+pub fn salt_of(data: &[u8]) -> Option<Salt> {
+    data.strip_prefix(MAGIC)?.get(..SALT_LEN)?.try_into().ok()
+}
+
+/// The salt of the encrypted file at `path`, or a new one if it is not encrypted yet. Reusing
+/// the salt keeps the file unchanged when its contents are.
+pub fn salt_for(path: impl AsRef<Path>) -> Salt {
+    fs::read(path)
+        .ok()
+        .and_then(|data| salt_of(&data))
+        .unwrap_or_else(new_salt)
+}
+
+/// Encrypts `plaintext`. The nonce is derived from the key and the plaintext, so encrypting the
+/// same contents with the same salt gives the same file and re-encrypting does not show up as a
+/// change.
+pub fn encrypt(key: &Key, salt: &Salt, plaintext: &[u8]) -> Vec<u8> {
+    let derived = key.derive(salt);
+    let digest = Sha256::new()
+        .chain_update(b"nonce\0")
+        .chain_update(derived)
+        .chain_update(plaintext)
+        .finalize();
+    let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+    let ciphertext = cipher(derived)
+        .encrypt(nonce, plaintext)
+        .expect("plaintext is too large to encrypt");
+
+    [MAGIC, salt, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Decrypts data produced by [`encrypt`].
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    let data = data.strip_prefix(MAGIC).ok_or(EncryptionError::WrongKey)?;
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(EncryptionError::WrongKey);
+    }
+    let (salt, data) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher(key.derive(salt.try_into().unwrap()))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| EncryptionError::WrongKey)
+}
+
+/// Reads a file, decrypting it with the key from [`KEY_VAR`] if it is encrypted.
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let data = fs::read(path)?;
+    if !is_encrypted(&data) {
+        return Ok(data);
+    }
+    let key = Key::from_env().ok_or_else(|| io::Error::other(EncryptionError::MissingKey))?;
+    decrypt(&key, &data).map_err(io::Error::other)
+}
+
+/// Like [`read`], for text files.
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes a file, encrypting it if [`KEY_VAR`] is set.
+pub fn write(path: impl AsRef<Path>, contents: &[u8]) -> io::Result<()> {
+    match Key::from_env() {
+        Some(key) => {
+            let salt = salt_for(&path);
+            fs::write(path, encrypt(&key, &salt, contents))
+        }
+        None => fs::write(path, contents),
+    }
+}
+
+/// Encrypts a plain file in place if [`KEY_VAR`] is set. Returns whether it was encrypted.
+pub fn seal(path: impl AsRef<Path>) -> io::Result<bool> {
+    let Some(key) = Key::from_env() else {
+        return Ok(false);
+    };
+    let data = fs::read(&path)?;
+    if data.is_empty() || is_encrypted(&data) {
+        return Ok(false);
+    }
+    fs::write(path, encrypt(&key, &new_salt(), &data))?;
+    Ok(true)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const SALT: Salt = [7; SALT_LEN];
+
+    #[test]
+    fn round_trips() {
+        let key = Key::new("hunter2");
+        let encrypted = encrypt(&key, &SALT, b"1abc2\npqr3stu8vwx\n");
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(b"1abc2\n"));
+        assert_eq!(salt_of(&encrypted), Some(SALT));
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), b"1abc2\npqr3stu8vwx\n");
+
+        // stable output, so re-encrypting unchanged files does not create a diff.
+        assert_eq!(encrypt(&key, &SALT, b"1abc2\npqr3stu8vwx\n"), encrypted);
+        assert_ne!(encrypt(&key, &SALT, b"1abc2\n"), encrypted);
+        assert_ne!(
+            encrypt(&key, &[8; SALT_LEN], b"1abc2\npqr3stu8vwx\n"),
+            encrypted
+        );
+        assert_ne!(new_salt(), new_salt());
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_tampering() {
+        let encrypted = encrypt(&Key::new("hunter2"), &SALT, b"input");
+        assert_eq!(
+            decrypt(&Key::new("hunter3"), &encrypted),
+            Err(EncryptionError::WrongKey)
+        );
+
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            decrypt(&Key::new("hunter2"), &tampered),
+            Err(EncryptionError::WrongKey)
+        );
+        assert_eq!(
+            decrypt(&Key::new("hunter2"), MAGIC),
+            Err(EncryptionError::WrongKey)
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::template::{Day, encryption};
+
+/// How [`try_read_file`] treats the contents of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadMode {
+    /// The file as it is on disk.
+    #[default]
+    Raw,
+    /// Without byte order mark, with `\n` line endings and exactly one trailing newline.
+    Normalised,
+}
+
+/// Why an input or example file could not be used.
+#[derive(Debug)]
+pub enum InputError {
+    Unreadable { path: PathBuf, source: io::Error },
+    Empty { path: PathBuf },
+    ErrorPage { path: PathBuf },
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Unreadable { path, source } => {
+                write!(f, "could not read \"{}\": {source}", path.display())
+            }
+            InputError::Empty { path } => write!(
+                f,
+                "\"{}\" is empty, download it with `cargo download <day>` or paste it in",
+                path.display()
+            ),
+            InputError::ErrorPage { path } => write!(
+                f,
+                "\"{}\" contains an error page instead of a puzzle input, check your session cookie and download it again",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Strips a byte order mark, converts CRLF line endings and ensures exactly one trailing
+/// newline. Empty contents stay empty.
+pub fn normalise(contents: &str) -> String {
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+    let mut normalised = contents.replace("\r\n", "\n");
+    normalised.truncate(normalised.trim_end_matches('\n').len());
+    if !normalised.is_empty() {
+        normalised.push('\n');
+    }
+    normalised
+}
+
+/// Whether the contents look like the response of a failed download rather than a puzzle input.
+fn is_error_page(contents: &str) -> bool {
+    let start = contents.trim_start().to_ascii_lowercase();
+    start.starts_with("<!doctype html")
+        || start.starts_with("<html")
+        || start.starts_with("puzzle inputs differ by user")
+        || start.starts_with("please don't repeatedly request this endpoint")
+        || start.starts_with("404 not found")
+}
+
+fn read(path: PathBuf, is_input: bool, mode: ReadMode) -> Result<String, InputError> {
+    let contents = match encryption::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(source) => return Err(InputError::Unreadable { path, source }),
+    };
+
+    // empty examples are fine, they are created by `scaffold` and filled in by hand.
+    if is_input && contents.trim().is_empty() {
+        return Err(InputError::Empty { path });
+    }
+    if is_error_page(&contents) {
+        return Err(InputError::ErrorPage { path });
+    }
+
+    Ok(match mode {
+        ReadMode::Raw => contents,
+        ReadMode::Normalised => normalise(&contents),
+    })
+}
+
+/// A file in `data/`, which is located relative to the crate so binaries can run from anywhere.
+fn data_path(folder: &str, file_name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join(folder)
+        .join(file_name)
+}
+
+/// Reads `data/<folder>/<day>.txt`, rejecting empty inputs and error pages.
+pub fn try_read_file(folder: &str, day: Day, mode: ReadMode) -> Result<String, InputError> {
+    read(
+        data_path(folder, &format!("{day}.txt")),
+        folder == "inputs",
+        mode,
+    )
+}
+
+/// Like [`try_read_file`], with a part suffix. E.g. like `01-2.txt`.
+pub fn try_read_file_part(
+    folder: &str,
+    day: Day,
+    part: u8,
+    mode: ReadMode,
+) -> Result<String, InputError> {
+    read(
+        data_path(folder, &format!("{day}-{part}.txt")),
+        folder == "inputs",
+        mode,
+    )
+}
+
+/// The additional inputs of a day, `data/inputs/<day>/<name>.txt`, as `(name, path)` sorted by
+/// name. These are e.g. the personal inputs of each team member.
+pub fn named_inputs(day: Day) -> Vec<(String, PathBuf)> {
+    let mut inputs: Vec<(String, PathBuf)> = fs::read_dir(data_path("inputs", &day.to_string()))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path
+                .file_name()?
+                .to_str()?
+                .strip_suffix(".txt")?
+                .to_string();
+            Some((name, path))
+        })
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+/// `value` if it is an existing file, otherwise the named input `data/inputs/<day>/<value>.txt`.
+pub fn input_path(day: Day, value: &str) -> PathBuf {
+    let path = PathBuf::from(value);
+    if path.is_file() {
+        path
+    } else {
+        data_path("inputs", &format!("{day}/{value}.txt"))
+    }
+}
+
+/// Where a solution reads its input from, selected with the arguments of its binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/inputs/<day>.txt`.
+    Default,
+    /// `--input <name|path>`, see [`input_path`].
+    Named(String),
+    /// `--example [n]`: `data/examples/<day>.txt`, or `<day>-<n>.txt` for `n` > 1.
+    Example(u8),
+    /// `-` or `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the source from arguments like `["01", "--example", "2"]`.
+    pub fn from_args(args: &[String]) -> Self {
+        for (i, arg) in args.iter().enumerate() {
+            let value = args.get(i + 1).map(String::as_str);
+            match (arg.as_str(), value) {
+                ("--input", Some("-")) | ("-", _) => return InputSource::Stdin,
+                ("--input", value) => return InputSource::Named(value.unwrap_or("").into()),
+                ("--example", value) => {
+                    let n = value.and_then(|v| v.parse().ok()).unwrap_or(1);
+                    return InputSource::Example(n);
+                }
+                _ => {}
+            }
+        }
+        InputSource::Default
+    }
+
+    pub fn read(&self, day: Day) -> Result<String, InputError> {
+        match self {
+            InputSource::Default => try_read_file("inputs", day, ReadMode::Raw),
+            InputSource::Named(value) => read(input_path(day, value), true, ReadMode::Raw),
+            InputSource::Example(0 | 1) => try_read_file("examples", day, ReadMode::Raw),
+            InputSource::Example(n) => try_read_file_part("examples", day, *n, ReadMode::Raw),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                match io::stdin().read_to_string(&mut input) {
+                    Ok(_) => Ok(input),
+                    Err(source) => Err(InputError::Unreadable {
+                        path: "<stdin>".into(),
+                        source,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Reads the input a solution runs on, `data/inputs/<day>.txt` unless the arguments of the
+/// binary select another [`InputSource`].
+pub fn read_input(day: Day) -> Result<String, InputError> {
+    let args: Vec<String> = env::args().collect();
+    InputSource::from_args(&args).read(day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::template::read_file;
+
+    #[test]
+    fn normalises_contents() {
+        assert_eq!(normalise("\u{feff}a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalise("a\nb"), "a\nb\n");
+        assert_eq!(normalise("a\n\n\n"), "a\n");
+        assert_eq!(normalise("  a\n  b  \n"), "  a\n  b  \n");
+        assert_eq!(normalise(""), "");
+    }
+
+    #[test]
+    fn detects_error_pages() {
+        assert!(is_error_page("<!DOCTYPE html>\n<html lang=\"en-us\">"));
+        assert!(is_error_page(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ));
+        assert!(!is_error_page("1abc2\npqr3stu8vwx\n"));
+        assert!(!is_error_page("<<>>\n"));
+    }
+
+    #[test]
+    fn resolves_named_inputs() {
+        let day = crate::day!(1);
+        assert!(input_path(day, "alice").ends_with("data/inputs/01/alice.txt"));
+        assert_eq!(
+            input_path(day, "data/examples/01.txt"),
+            PathBuf::from("data/examples/01.txt")
+        );
+        assert!(named_inputs(crate::day!(25)).is_empty());
+    }
+
+    #[test]
+    fn selects_input_sources() {
+        let source = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            InputSource::from_args(&args)
+        };
+        assert_eq!(source(&["01"]), InputSource::Default);
+        assert_eq!(source(&["01", "--time"]), InputSource::Default);
+        assert_eq!(
+            source(&["01", "--input", "alice"]),
+            InputSource::Named("alice".into())
+        );
+        assert_eq!(source(&["01", "--input", "-"]), InputSource::Stdin);
+        assert_eq!(source(&["01", "-"]), InputSource::Stdin);
+        assert_eq!(source(&["01", "--example"]), InputSource::Example(1));
+        assert_eq!(
+            source(&["01", "--example", "2", "--time"]),
+            InputSource::Example(2)
+        );
+        assert_eq!(
+            source(&["01", "--example", "--time"]),
+            InputSource::Example(1)
+        );
+    }
+
+    #[test]
+    fn reads_examples() {
+        let example = InputSource::Example(1).read(crate::day!(1)).unwrap();
+        assert_eq!(example, read_file("examples", crate::day!(1)));
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let err = try_read_file_part("examples", crate::day!(25), 9, ReadMode::Raw).unwrap_err();
+        assert!(matches!(err, InputError::Unreadable { .. }));
+        assert!(err.to_string().contains("25-9.txt"));
+    }
+}
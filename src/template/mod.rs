@@ -1,68 +1,170 @@
-use std::{env, fs};
-
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod debug;
+pub mod encryption;
+pub mod puzzle;
+pub mod registry;
+pub mod rng;
 pub mod runner;
 
 pub use day::*;
+pub use day_selector::*;
+pub use input::*;
+pub use solution_meta::PART_FNS;
 
 mod day;
+mod day_selector;
+mod input;
 mod readme_benchmarks;
 mod run_multi;
+mod solution_meta;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// Panics with a description of the problem if the file is missing, or is an empty input or
+/// an error page from a failed download.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day, ReadMode::Raw).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`read_file`], but normalised: without byte order mark, with `\n` line endings and
+/// exactly one trailing newline.
+#[must_use]
+pub fn read_file_normalised(folder: &str, day: Day) -> String {
+    try_read_file(folder, day, ReadMode::Normalised).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file_part(folder, day, part, ReadMode::Raw).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`read_file_part`], but normalised like [`read_file_normalised`].
+#[must_use]
+pub fn read_file_part_normalised(folder: &str, day: Day, part: u8) -> String {
+    try_read_file_part(folder, day, part, ReadMode::Normalised).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// It can be followed by metadata for the registry: `solution!(4, title = "...", tags = ["grid"])`.
+/// `variants = [part_two_fast]` registers alternative implementations of a part, which run
+/// instead of the parts with `--variant <name>` and are compared by the `check` command.
+/// The metadata is declared as `META`, which a generated test compares with what
+/// [`registry`](crate::template::registry) reads from the source.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2];;; [] []);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1];;; [] []);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2];;; [] []);
+    };
+    ($day:expr, $($key:ident = $value:tt),+ $(,)?) => {
+        $crate::solution!(@meta $day, [[part_one, 1] [part_two, 2]], [], [], [], [], $($key = $value),+);
+    };
+    ($day:expr, 1, $($key:ident = $value:tt),+ $(,)?) => {
+        $crate::solution!(@meta $day, [[part_one, 1]], [], [], [], [], $($key = $value),+);
+    };
+    ($day:expr, 2, $($key:ident = $value:tt),+ $(,)?) => {
+        $crate::solution!(@meta $day, [[part_two, 2]], [], [], [], [], $($key = $value),+);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // the keys are collected one by one, in the order: title, tags, generator, variants.
+    (@meta $day:expr, [$($parts:tt)*], [$($title:literal)?], [$($tag:literal)*], [$($generator:ident)?], [$($variant:ident)*] $(,)?) => {
+        $crate::solution!(@impl $day, $($parts)*; $($generator)?; $($variant)*; [$($title)?] [$($tag)*]);
+    };
+    (@meta $day:expr, $parts:tt, $_title:tt, $tags:tt, $generator:tt, $variants:tt, title = $title:literal $(, $($rest:tt)*)?) => {
+        $crate::solution!(@meta $day, $parts, [$title], $tags, $generator, $variants $(, $($rest)*)?);
+    };
+    (@meta $day:expr, $parts:tt, $title:tt, $_tags:tt, $generator:tt, $variants:tt, tags = [$($tag:literal),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::solution!(@meta $day, $parts, $title, [$($tag)*], $generator, $variants $(, $($rest)*)?);
+    };
+    (@meta $day:expr, $parts:tt, $title:tt, $tags:tt, $_generator:tt, $variants:tt, generator = $generator:ident $(, $($rest:tt)*)?) => {
+        $crate::solution!(@meta $day, $parts, $title, $tags, [$generator], $variants $(, $($rest)*)?);
+    };
+    (@meta $day:expr, $parts:tt, $title:tt, $tags:tt, $generator:tt, $_variants:tt, variants = [$($variant:ident),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::solution!(@meta $day, $parts, $title, $tags, $generator, [$($variant)*] $(, $($rest)*)?);
+    };
+
+    (@option) => { None };
+    (@option $value:expr) => { Some($value) };
+    (@present) => { false };
+    (@present $value:tt) => { true };
+
+    (@impl $day:expr, $( [$func:expr, $part:expr] )*; $($generator:ident)?; $($variant:ident)*; [$($title:literal)?] [$($tag:literal)*]) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The metadata given to `solution!`.
+        #[allow(dead_code)]
+        pub const META: $crate::template::registry::Meta = $crate::template::registry::Meta {
+            title: $crate::solution!(@option $($title)?),
+            tags: &[$($tag),*],
+            runs: &[$($part),*],
+            generator: $crate::solution!(@present $($generator)?),
+            variants: &[$(stringify!($variant)),*],
+        };
+
+        /// Runs the parts on `input`, as `main` does without `--variant`.
+        pub fn run_parts(input: &str) {
+            $( $crate::template::runner::run_part($func, input, DAY, $part); )*
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn registry_reads_the_solution_macro() {
+            $crate::template::registry::assert_matches(DAY, &META);
+        }
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $(
+                if run_generator($generator) {
+                    return;
+                }
+            )?
+            let input = match $crate::template::read_input(DAY) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            };
+            match selected_variant().as_deref() {
+                None => run_parts(&input),
+                $(
+                    Some(stringify!($variant)) => {
+                        const PART: u8 = variant_part(stringify!($variant));
+                        run_part($variant, &input, DAY, PART);
+                    }
+                )*
+                Some(name) => {
+                    eprintln!("Error: day {DAY} has no variant `{name}`.");
+                    std::process::exit(1);
+                }
+            }
         }
     };
 }
@@ -0,0 +1,210 @@
+/// Helpers for reading the puzzle descriptions that `download` stores in `data/puzzles`.
+use regex::Regex;
+
+use crate::template::{Day, encryption};
+
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+/// The stored puzzle description for a day, if it has been downloaded.
+pub fn read_puzzle(day: Day) -> Option<String> {
+    encryption::read_to_string(get_puzzle_path(day)).ok()
+}
+
+/// Extracts the title from a puzzle's `--- Day 1: Title ---` heading.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        // aoc-cli escapes the dashes and may render the heading with `#` or `>` prefixes.
+        let line = line.replace("\\-", "-");
+        let heading = line
+            .trim_start_matches(['#', '>', '\\', ' '])
+            .trim_end()
+            .strip_prefix("--- Day ")?
+            .strip_suffix("---")?;
+        let (_, title) = heading.split_once(':')?;
+        let title = title.trim();
+        (!title.is_empty()).then(|| title.to_string())
+    })
+}
+
+/// Splits a puzzle into the text of part one and, once unlocked, part two.
+pub fn split_parts(markdown: &str) -> (&str, Option<&str>) {
+    let heading = markdown
+        .match_indices("Part Two ---")
+        .next()
+        .map(|(i, _)| markdown[..i].rfind('\n').map_or(0, |start| start + 1));
+    match heading {
+        Some(i) => (&markdown[..i], Some(&markdown[i..])),
+        None => (markdown, None),
+    }
+}
+
+/// The contents of all fenced code blocks, each ending with a line break.
+pub fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(String::new()),
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    blocks
+}
+
+/// The block most likely to be the example input: the first one spanning several lines.
+pub fn likely_example(blocks: &[String]) -> Option<usize> {
+    blocks
+        .iter()
+        .position(|block| block.lines().count() > 1)
+        .or_else(|| (!blocks.is_empty()).then_some(0))
+}
+
+/// The answer for the example in a part's text, which is by convention the last emphasised
+/// code span, e.g. `` `*142*` ``.
+pub fn example_answer(part: &str) -> Option<String> {
+    let re = Regex::new(r"`\*{1,2}([^`*]+)\*{1,2}`|\*{1,2}`([^`]+)`\*{1,2}").unwrap();
+    re.captures_iter(part)
+        .last()
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().trim().to_string())
+}
+
+/// The answer for the `n`-th code block of a part (starting at 0), the last emphasised code
+/// span between that block and the next one.
+pub fn block_answer(part: &str, n: usize) -> Option<String> {
+    let mut fences = 0;
+    let text: String = part
+        .lines()
+        .filter(|line| {
+            let is_fence = line.trim_start().starts_with("```");
+            fences += usize::from(is_fence);
+            !is_fence && fences == 2 * (n + 1)
+        })
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    example_answer(&text)
+}
+
+/// Formats an answer as the rust expression a generated test compares against. Unless the
+/// template's parts return strings, a non-integer answer cannot be compared with, so the test
+/// expects `None` and keeps the answer in a comment.
+pub fn answer_expr(answer: Option<&str>, string_answers: bool) -> String {
+    match answer {
+        Some(answer) if string_answers => format!("Some({answer:?})"),
+        Some(answer) if answer.parse::<i64>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("None /* expected: {} */", answer.replace("*/", "* /")),
+        None => "None".into(),
+    }
+}
+
+/// The accepted answers aoc-cli records in the puzzle once a part is solved, in part order.
+pub fn submitted_answers(markdown: &str) -> Vec<String> {
+    let re = Regex::new(r"Your puzzle answer was `([^`]+)`").unwrap();
+    re.captures_iter(markdown)
+        .map(|c| c[1].trim().to_string())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = r#"\--- Day 1: Trebuchet?! ---
+----------
+
+Something is wrong with `global` snow production.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the values are `12` and `38`. Adding these together produces `*142*`.
+
+Your puzzle answer was `54601`.
+
+\--- Part Two ---
+----------
+
+Now with words:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`281`*.
+"#;
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(
+            title("\\--- Day 1: Secret Entrance ---\n----------\n\nText").as_deref(),
+            Some("Secret Entrance")
+        );
+        assert_eq!(
+            title("## \\-\\-\\- Day 12: Hot Springs \\-\\-\\-\n").as_deref(),
+            Some("Hot Springs")
+        );
+        assert_eq!(title("No heading here"), None);
+    }
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let (one, two) = split_parts(PUZZLE);
+        assert!(one.ends_with("`54601`.\n\n"));
+        assert!(two.unwrap().starts_with("\\--- Part Two ---"));
+        assert_eq!(split_parts("no parts").1, None);
+
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(
+            blocks,
+            vec!["1abc2\npqr3stu8vwx\n", "two1nine\neightwothree\n"]
+        );
+        assert_eq!(likely_example(&blocks), Some(0));
+        assert_eq!(likely_example(&["x\n".to_string()]), Some(0));
+        assert_eq!(likely_example(&[]), None);
+
+        assert_eq!(example_answer(one).as_deref(), Some("142"));
+        assert_eq!(example_answer(two.unwrap()).as_deref(), Some("281"));
+        assert_eq!(example_answer("nothing `emphasised`"), None);
+
+        let larger = format!("{one}```\nabc3\nxyz9\n```\n\nThis one gives `*72*`.\n");
+        assert_eq!(block_answer(&larger, 0).as_deref(), Some("142"));
+        assert_eq!(block_answer(&larger, 1).as_deref(), Some("72"));
+        assert_eq!(block_answer(&larger, 2), None);
+    }
+
+    #[test]
+    fn formats_answers() {
+        assert_eq!(answer_expr(Some("142"), false), "Some(142)");
+        assert_eq!(
+            answer_expr(Some("ab,c"), false),
+            "None /* expected: ab,c */"
+        );
+        assert_eq!(answer_expr(Some("142"), true), "Some(\"142\")");
+        assert_eq!(answer_expr(Some("ab,c"), true), "Some(\"ab,c\")");
+        assert_eq!(answer_expr(None, true), "None");
+    }
+
+    #[test]
+    fn extracts_submitted_answers() {
+        assert_eq!(submitted_answers(PUZZLE), vec!["54601"]);
+        let solved = format!("{PUZZLE}\nYour puzzle answer was `54078`.\n");
+        assert_eq!(submitted_answers(&solved), vec!["54601", "54078"]);
+        assert!(submitted_answers("no answers").is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+/// Registry of the solutions in `src/bin`.
+/// Collects the metadata passed to `solution!` together with the part signatures of every day
+/// the first time it is needed, so tools can list days without spawning their binaries.
+use std::{fs, path::Path, sync::OnceLock};
+
+use crate::template::{
+    Day,
+    solution_meta::{self, BIN_DIR, SolutionMeta},
+};
+
+/// The metadata `solution!` declares as `META` in a solution module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Meta {
+    pub title: Option<&'static str>,
+    pub tags: &'static [&'static str],
+    /// Parts that `solution!` runs.
+    pub runs: &'static [u8],
+    pub generator: bool,
+    pub variants: &'static [&'static str],
+}
+
+/// Metadata about a scaffolded day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayInfo {
+    pub day: Day,
+    /// Title given with `solution!(N, title = "...")`.
+    pub title: Option<String>,
+    /// Parts that `solution!` runs, e.g. only `[1]` with `solution!(N, 1)`.
+    pub runs: Vec<u8>,
+    /// Parts that `solution!` runs and that do more than return `None`.
+    pub parts: Vec<u8>,
+    /// Tags given with `solution!(N, tags = ["..."])`.
+    pub tags: Vec<String>,
+    /// The `T` in the `Option<T>` returned by part one, e.g. `u64`.
+    pub answer_type: String,
+    /// Whether an input generator is registered with `solution!(N, generator = generate)`.
+    pub generator: bool,
+    /// Alternative implementations given with `solution!(N, variants = [part_two_fast])`.
+    pub variants: Vec<String>,
+}
+
+impl DayInfo {
+    fn from_meta(meta: SolutionMeta) -> Option<Self> {
+        Some(Self {
+            day: Day::new(meta.day)?,
+            title: meta.title,
+            runs: meta.runs,
+            parts: meta.parts,
+            tags: meta.tags,
+            answer_type: meta.answer_type,
+            generator: meta.generator,
+            variants: meta.variants,
+        })
+    }
+}
+
+/// Reads the days from the sources in this crate, so tools find them from any directory.
+fn read_days() -> Vec<DayInfo> {
+    let bin_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(BIN_DIR);
+    let mut days: Vec<DayInfo> = fs::read_dir(bin_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let day = solution_meta::day_of_file(entry.file_name().to_str()?)?;
+            let source = fs::read_to_string(entry.path()).ok()?;
+            DayInfo::from_meta(solution_meta::parse(day, &source)?)
+        })
+        .collect();
+    days.sort_by_key(|info| info.day);
+    days
+}
+
+/// All scaffolded days in ascending order.
+pub fn days() -> &'static [DayInfo] {
+    static DAYS: OnceLock<Vec<DayInfo>> = OnceLock::new();
+    DAYS.get_or_init(read_days)
+}
+
+/// Metadata for `day`, if it has been scaffolded.
+pub fn get(day: Day) -> Option<&'static DayInfo> {
+    days().iter().find(|info| info.day == day)
+}
+
+pub fn is_scaffolded(day: Day) -> bool {
+    get(day).is_some()
+}
+
+/// Checks that the registry reads the same metadata from the source of `day` as its
+/// `solution!` declares in `META`. Every solution module runs this as a test.
+///
+/// # Panics
+/// Panics if the day is missing from the registry or its metadata differs.
+pub fn assert_matches(day: Day, meta: &Meta) {
+    let info = get(day).unwrap_or_else(|| panic!("the registry does not list day {day}"));
+    let title = meta.title.filter(|title| !title.is_empty());
+    assert_eq!(info.title.as_deref(), title, "title of day {day}");
+    assert_eq!(info.tags, meta.tags, "tags of day {day}");
+    assert_eq!(info.runs, meta.runs, "parts of day {day}");
+    assert_eq!(info.generator, meta.generator, "generator of day {day}");
+    assert_eq!(info.variants, meta.variants, "variants of day {day}");
+}
+
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{day}.rs")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{BIN_DIR, DayInfo};
+    use crate::{
+        day,
+        template::solution_meta::{day_of_file, parse},
+    };
+
+    const SOURCE: &str = r##"
+advent_of_code::solution!(
+    8,
+    // title = "Commented Out",
+    /* tags = [ /* "nested" */ ], */
+    title = r#"The "Quoted" Title, Part 1"#,
+    tags = ["graph", "union-find"],
+    generator = generate,
+    variants = [part_two_kruskal, part_one_chars],
+);
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let quote = '"';
+    input.split(quote).count().try_into().ok()
+}
+
+pub fn part_one_chars<'a>(input: &'a str) -> Option<u64> {
+    let braces = ['{', '\'', '}', '}'];
+    let text = r"fn part_two() { { ";
+    input.matches(&braces[..]).count().checked_add(text.len()).map(|n| n as u64)
+}
+
+pub fn part_two(_input: &str) -> Option<u64> {
+    todo!("part two is not unlocked yet")
+}
+
+pub fn part_two_kruskal(_input: &str) -> Option<u64> {
+    None
+}
+"##;
+
+    #[test]
+    fn reads_metadata_from_source() {
+        let info = DayInfo::from_meta(parse(8, SOURCE).unwrap()).unwrap();
+        assert_eq!(
+            info,
+            DayInfo {
+                day: day!(8),
+                title: Some("The \"Quoted\" Title, Part 1".into()),
+                runs: vec![1, 2],
+                parts: vec![1],
+                tags: vec!["graph".into(), "union-find".into()],
+                answer_type: "u64".into(),
+                generator: true,
+                variants: vec!["part_two_kruskal".into(), "part_one_chars".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn reads_only_the_given_parts() {
+        let info = parse(
+            3,
+            "solution!(3, 1);\nfn part_one(_: &str) -> Option<String> { todo!() }",
+        );
+        let info = info.unwrap();
+        assert_eq!(info.runs, [1]);
+        assert!(info.parts.is_empty());
+        assert_eq!(info.answer_type, "String");
+        assert!(parse(3, "fn main() {}").is_none());
+        assert!(parse(3, r#"const S: &str = "solution!(3)";"#).is_none());
+    }
+
+    /// The solution modules check their metadata against `META` in their own tests, this checks
+    /// that none of them is missing from the registry.
+    #[test]
+    fn reads_every_solution() {
+        let bin_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(BIN_DIR);
+        for entry in fs::read_dir(bin_dir).unwrap().flatten() {
+            let Some(day) = day_of_file(entry.file_name().to_str().unwrap()) else {
+                continue;
+            };
+            let info = super::get(crate::template::Day::new(day).unwrap());
+            assert!(info.is_some(), "day {day} is missing from the registry");
+        }
+    }
+}
@@ -1,13 +1,19 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, fs, io, process::ExitStatus};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, named_inputs, registry};
 
 use super::{
     all_days,
+    commands::generate,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    input: Option<&str>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,7 +30,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(day, is_timed, is_release, input).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -46,11 +52,187 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
-#[allow(dead_code)]
+/// Runs each day against its default input and every named input in `data/inputs/<day>/`, then
+/// prints the answers and times for each input side by side.
+pub fn run_each_input(days: &[Day], is_release: bool, is_timed: bool) {
+    for (i, &day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if !registry::is_scaffolded(day) {
+            println!("Not solved.");
+            continue;
+        }
+
+        let default_input = format!("data/inputs/{day}.txt");
+        let mut inputs: Vec<(String, String)> = vec![];
+        if fs::metadata(&default_input).is_ok_and(|m| m.len() > 0) {
+            inputs.push(("default".into(), default_input));
+        }
+        inputs.extend(
+            named_inputs(day)
+                .into_iter()
+                .map(|(name, path)| (name, path.display().to_string())),
+        );
+        if inputs.is_empty() {
+            println!("No inputs.");
+            continue;
+        }
+
+        let mut rows = vec![["Input", "Part 1", "Time", "Part 2", "Time"].map(String::from)];
+        for (name, path) in inputs {
+            let output = match child_commands::capture_solution(
+                day,
+                is_timed,
+                is_release,
+                Some(&path),
+                None,
+            ) {
+                Ok(output) => output,
+                Err(e) => {
+                    eprintln!("Failed on {name}: {e}");
+                    vec![]
+                }
+            };
+            let [answer_1, answer_2] =
+                child_commands::parse_answers(&output).map(|a| a.unwrap_or("✖".into()));
+            let [time_1, time_2] =
+                child_commands::parse_part_times(&output).map(|t| t.unwrap_or_default());
+            rows.push([name, answer_1, time_1, answer_2, time_2]);
+        }
+
+        print_table(&rows);
+    }
+}
+
+/// Prints rows as aligned columns, the first row in bold.
+pub fn print_table<R: AsRef<[String]>>(rows: &[R]) {
+    let columns = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .filter_map(|row| row.as_ref().get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for (i, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row
+            .as_ref()
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let padding = width - cell.chars().count();
+                format!("{cell}{}", " ".repeat(padding))
+            })
+            .collect();
+        let line = line.join("  ");
+        if i == 0 {
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", line.trim_end());
+        } else {
+            println!("{}", line.trim_end());
+        }
+    }
+}
+
+/// The empirical exponent `k` of `time ~ size^k` between two measurements.
+pub fn growth_exponent((size_a, nanos_a): (usize, f64), (size_b, nanos_b): (usize, f64)) -> f64 {
+    let ratio = size_b as f64 / size_a as f64;
+    (nanos_b / nanos_a).ln() / ratio.ln()
+}
+
+/// Benches each day on generated inputs of increasing size and prints how the time of each part
+/// grows. The timings are also written to `data/generated/<day>-timings.csv` for plotting.
+pub fn run_sizes(days: &[Day], sizes: &[usize], seed: u64) {
+    for (i, &day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let mut measurements: Vec<(usize, [Option<f64>; 2])> = vec![];
+        let mut rows = vec![["Size", "Part 1", "Growth", "Part 2", "Growth"].map(String::from)];
+        for &size in sizes {
+            let path = match generate::generate(day, size, seed) {
+                Ok(path) => path,
+                Err(e) => {
+                    println!("{e}.");
+                    break;
+                }
+            };
+            let output = match child_commands::capture_solution(
+                day,
+                true,
+                true,
+                Some(&path.to_string_lossy()),
+                None,
+            ) {
+                Ok(output) => output,
+                Err(e) => {
+                    println!("Failed on size {size}: {e}");
+                    break;
+                }
+            };
+            let nanos = child_commands::parse_part_nanos(&output);
+            let times = child_commands::parse_part_times(&output);
+
+            let mut row = vec![size.to_string()];
+            for part in 0..2 {
+                let growth = measurements
+                    .last()
+                    .and_then(|(prev_size, prev)| Some(((*prev_size, prev[part]?), nanos[part]?)))
+                    .map(|(prev, nanos)| growth_exponent(prev, (size, nanos)))
+                    .map_or_else(String::new, |k| format!("n^{k:.2}"));
+                row.push(times[part].clone().unwrap_or("✖".into()));
+                row.push(growth);
+            }
+            rows.push(row.try_into().unwrap());
+            measurements.push((size, nanos));
+        }
+        print_table(&rows);
+
+        if measurements.is_empty() {
+            continue;
+        }
+        let mut csv = String::from("size,part_1_nanos,part_2_nanos\n");
+        for (size, nanos) in &measurements {
+            let [one, two] = nanos.map(|n| n.map(|n| n.to_string()).unwrap_or_default());
+            csv.push_str(&format!("{size},{one},{two}\n"));
+        }
+        let path = format!("{}/{day}-timings.csv", generate::GENERATED_DIR);
+        match fs::write(&path, csv) {
+            Ok(()) => println!("\nWrote the timings to \"{path}\"."),
+            Err(e) => eprintln!("\nFailed to write \"{path}\": {e}"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    /// The solution bin failed, with what it wrote to stderr.
+    BadExitStatus(ExitStatus, String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "the output of the solution could not be read"),
+            Error::IO(e) => write!(f, "the solution could not be run: {e}"),
+            Error::BadExitStatus(status, stderr) if stderr.is_empty() => {
+                write!(f, "the solution exited with {status}")
+            }
+            Error::BadExitStatus(status, stderr) => {
+                write!(f, "the solution exited with {status}:\n{stderr}")
+            }
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,43 +241,68 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{ANSI_BOLD, ANSI_RESET, Day, registry};
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Arguments for `cargo` to run the solution bin of a day.
+    fn solution_args(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        input: Option<&str>,
+        variant: Option<&str>,
+    ) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
+        args.push("--".into());
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
+        }
+
+        if let Some(input) = input {
+            args.extend(["--input".into(), input.into()]);
+        }
+
+        if let Some(variant) = variant {
+            args.extend(["--variant".into(), variant.into()]);
         }
 
+        args
+    }
+
+    /// Run the solution bin for a given day
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        input: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !registry::is_scaffolded(day) {
+            return Ok(vec![]);
+        }
+
+        let args = solution_args(day, is_timed, is_release, input, None);
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -128,6 +335,81 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Like [`run_solution`], but only returns the output instead of printing it. Fails with the
+    /// captured stderr if the solution does, e.g. because it panicked.
+    /// With a `variant`, only that alternative implementation of a part runs.
+    pub fn capture_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        input: Option<&str>,
+        variant: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        if !registry::is_scaffolded(day) {
+            return Ok(vec![]);
+        }
+
+        let output = Command::new("cargo")
+            .args(solution_args(day, is_timed, is_release, input, variant))
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(Error::BadExitStatus(output.status, stderr));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Extracts the answer of each part from the output of a solution bin.
+    pub fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+        let mut answers = [None, None];
+        for line in output {
+            // intermediate results are overwritten with a carriage return.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+            for (part, answer) in answers.iter_mut().enumerate() {
+                let Some(rest) = line.strip_prefix(&format!("Part {}: ", part + 1)) else {
+                    continue;
+                };
+                *answer = if let Some(rest) = rest.strip_prefix(ANSI_BOLD) {
+                    rest.split_once(ANSI_RESET).map(|(a, _)| a.to_string())
+                } else if rest.starts_with('▼') {
+                    // multi-line answers cannot be compared, but the part did return something.
+                    Some("▼".to_string())
+                } else {
+                    None
+                };
+            }
+        }
+        answers
+    }
+
+    /// The time printed after the answer of each part, benched or not, with its nanoseconds.
+    fn parse_part_timings(output: &[String]) -> [Option<(&str, f64)>; 2] {
+        let mut timings = [None, None];
+        for line in output {
+            let line = line.rsplit('\r').next().unwrap_or(line);
+            for (part, timing) in timings.iter_mut().enumerate() {
+                if line.starts_with(&format!("Part {}: ", part + 1)) && line.ends_with(')') {
+                    *timing = parse_time(line);
+                }
+            }
+        }
+        timings
+    }
+
+    /// The time printed after the answer of each part, benched or not.
+    pub fn parse_part_times(output: &[String]) -> [Option<String>; 2] {
+        parse_part_timings(output).map(|timing| timing.map(|(timing_str, _)| timing_str.into()))
+    }
+
+    /// Like [`parse_part_times`], in nanoseconds.
+    pub fn parse_part_nanos(output: &[String]) -> [Option<f64>; 2] {
+        parse_part_timings(output).map(|timing| timing.map(|(_, nanos)| nanos))
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
@@ -177,7 +459,8 @@ pub mod child_commands {
             .next_back()?
             .split('@')
             .next()?
-            .trim();
+            .trim()
+            .trim_end_matches(')');
 
         let parsed_timing = match str_timing {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
@@ -205,7 +488,8 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time, parse_part_nanos, parse_part_times};
+        use crate::template::{ANSI_BOLD, ANSI_RESET};
 
         use crate::day;
 
@@ -239,6 +523,42 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_answers_and_part_times() {
+            let output = vec![
+                format!(
+                    "Part 1: {ANSI_BOLD}142{ANSI_RESET}\rPart 1: {ANSI_BOLD}142{ANSI_RESET} (1.2ms)"
+                ),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ];
+            assert_eq!(parse_answers(&output), [Some("142".into()), None]);
+            assert_eq!(parse_part_times(&output), [Some("1.2ms".into()), None]);
+
+            let output = vec![
+                format!("Part 1: {ANSI_BOLD}ab{ANSI_RESET} (3.0µs @ 10000 samples)"),
+                "Part 2: ▼ (1.0ms @ 10 samples)".into(),
+                "#..".into(),
+            ];
+            assert_eq!(
+                parse_answers(&output),
+                [Some("ab".into()), Some("▼".into())]
+            );
+            assert_eq!(
+                parse_part_times(&output),
+                [Some("3.0µs".into()), Some("1.0ms".into())]
+            );
+            assert_eq!(parse_part_nanos(&output), [Some(3000.0), Some(1_000_000.0)]);
+            assert_eq!(parse_answers(&[]), [None, None]);
+        }
+
+        #[test]
+        fn estimates_growth() {
+            use super::super::growth_exponent;
+            assert_approx_eq!(growth_exponent((100, 5.0), (1000, 50.0)), 1.0);
+            assert_approx_eq!(growth_exponent((100, 5.0), (200, 20.0)), 2.0);
+            assert_approx_eq!(growth_exponent((10, 7.0), (20, 7.0)), 0.0);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
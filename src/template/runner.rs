@@ -7,13 +7,96 @@ use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::debug;
+use crate::template::rng::Rng;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, InputSource, aoc_cli};
+
+/// Generates an input with about `size` elements, registered with
+/// `solution!(N, generator = generate)`.
+pub type Generator = fn(usize, &mut Rng) -> String;
+
+pub const DEFAULT_SIZE: usize = 1000;
+pub const DEFAULT_SEED: u64 = 1;
+
+/// Prints a generated input instead of running the solution if the binary was called with
+/// `--generate [--size <n>] [--seed <s>]`. Returns whether it did.
+pub fn run_generator(generator: Generator) -> bool {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|x| x == "--generate") {
+        return false;
+    }
+
+    let value = |flag: &str| {
+        let i = args.iter().position(|x| x == flag)?;
+        args.get(i + 1)?.parse::<u64>().ok()
+    };
+    let size = value("--size").map_or(DEFAULT_SIZE, |size| size as usize);
+    let seed = value("--seed").unwrap_or(DEFAULT_SEED);
+
+    print!("{}", generator(size, &mut Rng::new(seed)));
+    true
+}
+
+/// The part a variant such as `part_two_fast` replaces. `solution!` evaluates this at compile
+/// time, so a variant with any other name is a build error.
+pub const fn variant_part(name: &str) -> u8 {
+    const fn starts_with(name: &[u8], prefix: &[u8]) -> bool {
+        if name.len() <= prefix.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < prefix.len() {
+            if name[i] != prefix[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    if starts_with(name.as_bytes(), b"part_one_") {
+        1
+    } else if starts_with(name.as_bytes(), b"part_two_") {
+        2
+    } else {
+        panic!("variant names must start with `part_one_` or `part_two_`")
+    }
+}
+
+/// The variant selected with `--variant <name>`, which runs instead of the parts.
+pub fn selected_variant() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let i = args.iter().position(|x| x == "--variant")?;
+    args.get(i + 1).cloned()
+}
+
+/// The part selected with `--profile <part>` and how often it runs, given with
+/// `--iterations <n>`. `cargo profile` runs the binary like this under `perf`.
+fn profiled_part() -> Option<(u8, u64)> {
+    let args: Vec<String> = env::args().collect();
+    let value = |flag: &str| {
+        let i = args.iter().position(|x| x == flag)?;
+        args.get(i + 1)?.parse::<u64>().ok()
+    };
+    let part = u8::try_from(value("--profile")?).ok()?;
+    Some((part, value("--iterations").unwrap_or(1)))
+}
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    if let Some((profiled, iterations)) = profiled_part() {
+        if profiled == part {
+            profile(func, input, iterations);
+            println!("Profiled part {part} with {iterations} iteration(s).");
+        }
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
+    crate::viz::begin(day, part);
     let (result, duration, samples) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
+    crate::viz::end();
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
@@ -61,11 +144,14 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
 
     let mut timers: Vec<Duration> = vec![];
 
+    // debug messages would flood the output and the timings.
+    debug::set_suppressed(true);
     for _ in 0..bench_iterations {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
+    debug::set_suppressed(false);
 
     (
         #[allow(clippy::cast_possible_truncation)]
@@ -74,6 +160,16 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     )
 }
 
+/// Runs a part `iterations` times without timing or printing it, so that a profiler only sees
+/// the part. With no iterations, only the setup of the binary runs.
+fn profile<I: Copy, T>(func: impl Fn(I) -> T, input: I, iterations: u64) {
+    debug::set_suppressed(true);
+    for _ in 0..iterations {
+        black_box(func(black_box(input)));
+    }
+    debug::set_suppressed(false);
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -155,6 +251,11 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if InputSource::from_args(&args) != InputSource::Default {
+        eprintln!("Not submitting, the answer was not computed from your puzzle input.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
@@ -0,0 +1,334 @@
+//! Reads the metadata of a solution from its source: the arguments of `solution!` and the
+//! signatures of its parts. Shared by [`registry`](super::registry) and the build script, which
+//! includes this file, so it only uses `std`. Each solution module checks in a test that this
+//! reads the same as the `META` its `solution!` declares.
+
+use std::ops::Range;
+
+/// Directory of the solution modules, relative to the manifest.
+pub const BIN_DIR: &str = "src/bin";
+
+/// The functions of part one and part two in a solution module.
+pub const PART_FNS: [&str; 2] = ["part_one", "part_two"];
+
+/// Metadata collected from a single `src/bin/NN.rs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionMeta {
+    pub day: u8,
+    pub title: Option<String>,
+    /// Parts that `solution!` runs.
+    pub runs: Vec<u8>,
+    /// Parts that are run and do more than return `None`.
+    pub parts: Vec<u8>,
+    pub tags: Vec<String>,
+    pub answer_type: String,
+    pub generator: bool,
+    pub variants: Vec<String>,
+}
+
+/// The day of a solution module named like `04.rs`.
+pub fn day_of_file(name: &str) -> Option<u8> {
+    let stem = name.strip_suffix(".rs")?;
+    let day: u8 = stem.parse().ok().filter(|d| (1..=25).contains(d))?;
+    (stem.len() == 2).then_some(day)
+}
+
+/// A source with its comments blanked out, together with a copy in which the contents of
+/// string and character literals are blanked out as well, so that every bracket and comma in
+/// the copy is code. Both keep the byte offsets of the source, so a span found in the copy
+/// can be read from the code.
+struct Source {
+    code: String,
+    skeleton: String,
+}
+
+/// Appends `text` with everything but line breaks replaced by spaces, keeping its length.
+fn push_blank(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\n' => out.push('\n'),
+            c => out.extend(std::iter::repeat_n(' ', c.len_utf8())),
+        }
+    }
+}
+
+/// The length of the `//` or (nested) `/* */` comment at the start of `text`.
+fn comment_len(text: &str) -> Option<usize> {
+    if text.starts_with("//") {
+        return Some(text.find('\n').unwrap_or(text.len()));
+    }
+    if !text.starts_with("/*") {
+        return None;
+    }
+    let (mut depth, mut i) = (1, 2);
+    while depth > 0 && i < text.len() {
+        if text[i..].starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if text[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    Some(i.min(text.len()))
+}
+
+/// The length of the string or character literal at the start of `text`, and the lengths of
+/// its opening and closing delimiters. `previous` is the character before it, which tells a
+/// prefix like the `r` in `r"..."` from the end of an identifier.
+fn literal_len(text: &str, previous: char) -> Option<(usize, usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    if !(previous.is_alphanumeric() || previous == '_') {
+        i += usize::from(matches!(bytes.first(), Some(b'b' | b'c')));
+        if bytes.get(i) == Some(&b'r') {
+            let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+            let open = i + 1 + hashes + 1;
+            if bytes.get(open - 1) != Some(&b'"') {
+                return None;
+            }
+            let close = format!("\"{}", "#".repeat(hashes));
+            let len = text[open..]
+                .find(&close)
+                .map_or(text.len(), |end| open + end + close.len());
+            return Some((len, open, close.len()));
+        }
+    }
+    match bytes.get(i) {
+        Some(b'"') => {
+            let mut escaped = false;
+            let end = text[i + 1..].find(|c| {
+                let end = c == '"' && !escaped;
+                escaped = c == '\\' && !escaped;
+                end
+            });
+            Some((end.map_or(text.len(), |end| i + 1 + end + 1), i + 1, 1))
+        }
+        // `'{'` and `'\''` are characters, while the `'a` in `&'a str` is a lifetime.
+        Some(b'\'') => {
+            let rest = &text[i + 1..];
+            let end = if rest.starts_with('\\') {
+                rest[2.min(rest.len())..].find('\'').map(|end| end + 2)
+            } else {
+                let c = rest.chars().next()?;
+                rest[c.len_utf8()..]
+                    .starts_with('\'')
+                    .then_some(c.len_utf8())
+            }?;
+            Some((i + 1 + end + 1, i + 1, 1))
+        }
+        _ => None,
+    }
+}
+
+impl Source {
+    fn new(source: &str) -> Self {
+        let mut code = String::with_capacity(source.len());
+        let mut skeleton = String::with_capacity(source.len());
+        let (mut rest, mut previous) = (source, ' ');
+        while let Some(c) = rest.chars().next() {
+            let len = if let Some(len) = comment_len(rest) {
+                push_blank(&mut code, &rest[..len]);
+                push_blank(&mut skeleton, &rest[..len]);
+                len
+            } else if let Some((len, open, close)) = literal_len(rest, previous) {
+                let close = len.saturating_sub(close).max(open);
+                code.push_str(&rest[..len]);
+                skeleton.push_str(&rest[..open]);
+                push_blank(&mut skeleton, &rest[open..close]);
+                skeleton.push_str(&rest[close..len]);
+                len
+            } else {
+                code.push(c);
+                skeleton.push(c);
+                c.len_utf8()
+            };
+            previous = rest[..len].chars().next_back().unwrap_or(' ');
+            rest = &rest[len..];
+        }
+        Self { code, skeleton }
+    }
+
+    /// The code in `span`, without surrounding whitespace.
+    fn text(&self, span: &Range<usize>) -> &str {
+        self.code[span.clone()].trim()
+    }
+
+    /// The span between the delimiter at `open` and its matching closing delimiter.
+    fn delimited(&self, open: usize, left: u8, right: u8) -> Option<Range<usize>> {
+        let mut depth = 0;
+        for (i, &b) in self.skeleton.as_bytes().iter().enumerate().skip(open) {
+            if b == left {
+                depth += 1;
+            } else if b == right {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + 1..i);
+                }
+            }
+        }
+        None
+    }
+
+    /// Splits the macro arguments in `span` on commas that are not nested in brackets.
+    fn split_args(&self, span: Range<usize>) -> Vec<Range<usize>> {
+        let mut args = vec![];
+        let (mut depth, mut start) = (0, span.start);
+        for (i, b) in self.skeleton[span.clone()].bytes().enumerate() {
+            match b {
+                b'[' | b'(' | b'{' => depth += 1,
+                b']' | b')' | b'}' => depth -= 1,
+                b',' if depth == 0 => {
+                    args.push(start..span.start + i);
+                    start = span.start + i + 1;
+                }
+                _ => {}
+            }
+        }
+        args.push(start..span.end);
+        args.retain(|arg| !self.text(arg).is_empty());
+        args
+    }
+
+    /// The elements of a list like `["a", "b"]` in `span`.
+    fn list(&self, span: Range<usize>) -> Vec<Range<usize>> {
+        let text = &self.skeleton[span.clone()];
+        match (text.find('['), text.rfind(']')) {
+            (Some(open), Some(close)) if open < close => {
+                self.split_args(span.start + open + 1..span.start + close)
+            }
+            _ => self.split_args(span),
+        }
+    }
+
+    /// Whether `fn name` exists and its body does more than give up, i.e. is not just `None`,
+    /// `return None` or a single `todo!(..)` or `unimplemented!(..)` as in the scaffolded
+    /// templates.
+    fn is_implemented(&self, name: &str) -> bool {
+        let Some(start) = self.skeleton.find(&format!("fn {name}(")) else {
+            return false;
+        };
+        let Some(open) = self.skeleton[start..].find('{').map(|i| start + i) else {
+            return false;
+        };
+        self.delimited(open, b'{', b'}').is_some_and(|body| {
+            let body: String = self.skeleton[body].split_whitespace().collect();
+            let body = body.trim_end_matches(';');
+            let gives_up = ["todo!", "unimplemented!"]
+                .iter()
+                .any(|name| body.starts_with(name) && !body.contains(';'));
+            !(gives_up || matches!(body, "None" | "returnNone"))
+        })
+    }
+
+    /// The `T` in the `-> Option<T>` return type of part one.
+    fn answer_type(&self) -> String {
+        self.skeleton
+            .find("fn part_one(")
+            .and_then(|start| {
+                let open = start + self.skeleton[start..].find("-> Option<")? + 9;
+                self.delimited(open, b'<', b'>')
+            })
+            .map_or_else(|| "unknown".to_string(), |ty| self.text(&ty).to_string())
+    }
+}
+
+/// The value of a string literal like `"a \"b\""` or `r#"a "b""#`.
+fn unquote(literal: &str) -> String {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return raw
+            .get(hashes + 1..raw.len().saturating_sub(hashes + 1))
+            .unwrap_or_default()
+            .to_string();
+    }
+    let inner = literal
+        .strip_prefix('"')
+        .and_then(|l| l.strip_suffix('"'))
+        .unwrap_or(literal);
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('0') => value.push('\0'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                value.extend(u8::from_str_radix(&hex, 16).ok().map(char::from));
+            }
+            Some('u') => {
+                let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let code = u32::from_str_radix(hex.trim_start_matches('{'), 16).ok();
+                value.extend(code.and_then(char::from_u32));
+            }
+            // a line break after a backslash is skipped together with the indentation.
+            Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            Some(c) => value.push(c),
+            None => {}
+        }
+    }
+    value
+}
+
+/// Reads the metadata of the solution for `day` from its source, `None` if it does not call
+/// `solution!`.
+pub fn parse(day: u8, source: &str) -> Option<SolutionMeta> {
+    let source = Source::new(source);
+    let start = source.skeleton.find("solution!(")?;
+    let args = source.delimited(start + "solution!".len(), b'(', b')')?;
+
+    let mut meta = SolutionMeta {
+        day,
+        title: None,
+        runs: vec![1, 2],
+        parts: vec![],
+        tags: vec![],
+        answer_type: source.answer_type(),
+        generator: false,
+        variants: vec![],
+    };
+
+    for arg in source.split_args(args).into_iter().skip(1) {
+        let Some(eq) = source.skeleton[arg.clone()].find('=') else {
+            meta.runs = source.text(&arg).parse().ok().into_iter().collect();
+            continue;
+        };
+        let value = arg.start + eq + 1..arg.end;
+        match source.text(&(arg.start..arg.start + eq)) {
+            "title" => meta.title = Some(unquote(source.text(&value))).filter(|t| !t.is_empty()),
+            "tags" => {
+                meta.tags = source
+                    .list(value)
+                    .iter()
+                    .map(|tag| unquote(source.text(tag)))
+                    .collect();
+            }
+            "generator" => meta.generator = true,
+            "variants" => {
+                meta.variants = source
+                    .list(value)
+                    .iter()
+                    .map(|variant| source.text(variant).to_string())
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    meta.parts = meta
+        .runs
+        .iter()
+        .copied()
+        .filter(|&part| source.is_implemented(PART_FNS[usize::from(part) - 1]))
+        .collect();
+
+    Some(meta)
+}
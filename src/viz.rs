@@ -0,0 +1,248 @@
+//! Frame capture for watching a solution work, instead of sprinkling `println!`.
+//!
+//! Solutions record frames with [`frame`] or [`grid`], and running the binary with
+//! `--visualize terminal` plays them back in the terminal while `--visualize frames` writes them
+//! to `data/frames/<day>/part<n>/` as PPM images. `cargo solve <day> --visualize <mode>` does the
+//! same.
+//!
+//! Frames are only recorded in debug builds without `--time`. In release builds, every function
+//! here is empty and the closures that render frames are never called, so they cost nothing when
+//! benchmarking.
+#[cfg(debug_assertions)]
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use crate::template::Day;
+
+/// Frames per second of the terminal playback, unless given with `--fps`.
+pub const DEFAULT_FPS: u32 = 10;
+
+/// Side length of a grid cell in the PPM frames, in pixels.
+#[cfg(debug_assertions)]
+const CELL_PIXELS: usize = 4;
+
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone)]
+enum Output {
+    Terminal { delay: Duration },
+    Frames,
+}
+
+#[cfg(debug_assertions)]
+struct Recording {
+    day: Day,
+    part: u8,
+    frames: usize,
+}
+
+#[cfg(debug_assertions)]
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+/// The output selected with the arguments of the binary, `None` if frames are not recorded.
+#[cfg(debug_assertions)]
+fn output() -> Option<&'static Output> {
+    static OUTPUT: OnceLock<Option<Output>> = OnceLock::new();
+    OUTPUT
+        .get_or_init(|| {
+            let args: Vec<String> = env::args().collect();
+            // frames would be recorded on every sample of the benchmark.
+            if args.iter().any(|arg| arg == "--time") {
+                return None;
+            }
+            let value = |flag: &str| {
+                let i = args.iter().position(|arg| arg == flag)?;
+                args.get(i + 1).map(String::as_str)
+            };
+            match value("--visualize")? {
+                "frames" => Some(Output::Frames),
+                _ => {
+                    let fps = value("--fps")
+                        .and_then(|fps| fps.parse().ok())
+                        .filter(|&fps| fps > 0)
+                        .unwrap_or(DEFAULT_FPS);
+                    let delay = Duration::from_secs(1) / fps;
+                    Some(Output::Terminal { delay })
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// Whether frames are recorded. Use it to skip work that only feeds the frames.
+#[cfg(debug_assertions)]
+pub fn enabled() -> bool {
+    output().is_some()
+}
+
+#[cfg(not(debug_assertions))]
+pub fn enabled() -> bool {
+    false
+}
+
+#[cfg(debug_assertions)]
+fn frames_dir(day: Day, part: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("data/frames")
+        .join(day.to_string())
+        .join(format!("part{part}"))
+}
+
+/// Starts recording the frames of a part. Called by the runner before each part.
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+pub fn begin(day: Day, part: u8) {
+    #[cfg(debug_assertions)]
+    if let Some(output) = output() {
+        // frames of an earlier run would be mixed up with the new ones.
+        if matches!(output, Output::Frames) {
+            let _ = fs::remove_dir_all(frames_dir(day, part));
+        }
+        *RECORDING.lock().unwrap() = Some(Recording {
+            day,
+            part,
+            frames: 0,
+        });
+    }
+}
+
+/// Stops recording and reports how many frames the part recorded. Called by the runner.
+pub fn end() {
+    #[cfg(debug_assertions)]
+    if let Some(recording) = RECORDING.lock().unwrap().take()
+        && recording.frames > 0
+    {
+        let Recording { day, part, frames } = recording;
+        match output() {
+            Some(Output::Frames) => eprintln!(
+                "Wrote {frames} frame(s) of part {part} to \"{}\".",
+                frames_dir(day, part).display()
+            ),
+            _ => eprintln!("Played {frames} frame(s) of part {part}."),
+        }
+    }
+}
+
+/// Records a frame of text, e.g. a grid with one line per row. `render` is only called when
+/// frames are recorded.
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+pub fn frame(render: impl FnOnce() -> String) {
+    #[cfg(debug_assertions)]
+    {
+        let Some(output) = output() else {
+            return;
+        };
+        let mut recording = RECORDING.lock().unwrap();
+        let Some(recording) = recording.as_mut() else {
+            return;
+        };
+        recording.frames += 1;
+
+        let text = render();
+        match output {
+            Output::Terminal { delay } => {
+                let mut stderr = io::stderr().lock();
+                let _ = write!(
+                    stderr,
+                    "\x1b[2J\x1b[H{text}\nDay {} part {} · frame {}\n",
+                    recording.day, recording.part, recording.frames
+                );
+                let _ = stderr.flush();
+                thread::sleep(*delay);
+            }
+            Output::Frames => {
+                let dir = frames_dir(recording.day, recording.part);
+                let path = dir.join(format!("frame-{:05}.ppm", recording.frames));
+                if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, ppm(&text)))
+                {
+                    eprintln!("Failed to write \"{}\": {e}", path.display());
+                }
+            }
+        }
+    }
+}
+
+/// Records a grid with `rows` and `cols`, drawing each cell with `cell`.
+pub fn grid(rows: usize, cols: usize, cell: impl Fn(usize, usize) -> char) {
+    frame(|| {
+        (0..rows)
+            .map(|row| (0..cols).map(|col| cell(row, col)).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+}
+
+/// The colour a character is drawn with in the PPM frames.
+#[cfg(debug_assertions)]
+fn colour(c: char) -> [u8; 3] {
+    match c {
+        '.' | ' ' => [16, 16, 24],
+        '#' => [200, 200, 200],
+        '@' => [240, 190, 60],
+        '|' | '-' => [90, 170, 255],
+        '^' => [230, 80, 80],
+        'S' | 'E' => [90, 220, 110],
+        c => {
+            // a stable, bright colour for any other character.
+            let [r, g, b, _] = u32::from(c).wrapping_mul(0x9e37_79b9).to_le_bytes();
+            [r, g, b].map(|x| x | 0x60)
+        }
+    }
+}
+
+/// Renders a text frame as a binary PPM image with a square block of pixels per character.
+#[cfg(debug_assertions)]
+fn ppm(text: &str) -> Vec<u8> {
+    let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let cols = lines.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let rows = lines.len().max(1);
+    let (width, height) = (cols * CELL_PIXELS, rows * CELL_PIXELS);
+
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for y in 0..height {
+        for x in 0..width {
+            let c = lines
+                .get(y / CELL_PIXELS)
+                .and_then(|line| line.get(x / CELL_PIXELS))
+                .copied()
+                .unwrap_or(' ');
+            image.extend_from_slice(&colour(c));
+        }
+    }
+    image
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "test_lib", debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_ppm_frames() {
+        let image = ppm("#.\n@");
+        let header = format!("P6\n{} {}\n255\n", 2 * CELL_PIXELS, 2 * CELL_PIXELS);
+        assert!(image.starts_with(header.as_bytes()));
+        assert_eq!(
+            image.len(),
+            header.len() + 3 * 4 * CELL_PIXELS * CELL_PIXELS
+        );
+
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels[..3], colour('#'));
+        assert_eq!(pixels[3 * CELL_PIXELS..3 * CELL_PIXELS + 3], colour('.'));
+        // the missing cell at the end of the short second line is blank.
+        assert_eq!(pixels[pixels.len() - 3..], colour(' '));
+    }
+
+    #[test]
+    fn skips_rendering_when_disabled() {
+        // the test binary is not run with `--visualize`.
+        assert!(!enabled());
+        frame(|| unreachable!("frames are not recorded"));
+    }
+}